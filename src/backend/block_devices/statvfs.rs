@@ -6,6 +6,8 @@ use std::io::Error;
 use std::mem;
 use std::path::Path;
 
+use crate::Bytes;
+
 // mut?
 // pub fn errno() -> &'static i32 {
 //     unsafe { &mut *libc::__errno_location() }
@@ -56,6 +58,16 @@ impl Statvfs {
             Err(Error::last_os_error())
         }
     }
+
+    /// size of filesystem (`f_blocks` * `f_frsize`)
+    pub fn total(&self) -> Bytes {
+        Bytes::from_blocks(self.f_blocks, self.f_frsize)
+    }
+
+    /// free space available to unprivileged users (`f_bavail` * `f_frsize`)
+    pub fn available(&self) -> Bytes {
+        Bytes::from_blocks(self.f_bavail, self.f_frsize)
+    }
 }
 
 #[test]
@@ -65,8 +77,8 @@ fn statvfs_test() {
     let stat = Statvfs::get("/dev/sda".to_string()).unwrap();
     println!(
         "{} {}",
-        b_to_gib(stat.f_bsize * stat.f_bavail),
-        b_to_gib(stat.f_bsize * stat.f_blocks),
+        b_to_gib(stat.available().as_u64()),
+        b_to_gib(stat.total().as_u64()),
     );
     dbg!(stat);
 }
@@ -41,7 +41,16 @@ pub(crate) const TEMP1_HIGHEST: &str = "temp1_highest";
 /// `/sys/block/<DEVICE>`/device/hwmon/hwmon*/temp1_lowest`
 pub(crate) const TEMP1_LOWEST: &str = "temp1_lowest";
 
-pub(crate) const BLOCK_SIZE_DEFAULT: usize = 512;
+/// `/sys/block/zram<N>`
+pub(crate) const ZRAM: &str = "zram";
+/// `/sys/block/zram<N>/disksize`
+pub(crate) const ZRAM_DISKSIZE: &str = "disksize";
+/// `/sys/block/zram<N>/comp_algorithm`
+pub(crate) const ZRAM_COMP_ALGORITHM: &str = "comp_algorithm";
+/// `/sys/block/zram<N>/mm_stat`
+pub(crate) const ZRAM_MM_STAT: &str = "mm_stat";
+/// `/sys/block/zram<N>/io_stat`
+pub(crate) const ZRAM_IO_STAT: &str = "io_stat";
 
 /// `/sys/block/<DEVICE>/holders/<CHILD_DEVICE>`
 pub(crate) const HOLDERS: &str = "holders";
@@ -1,6 +1,8 @@
 pub mod common;
 pub mod sys_block;
 pub mod sys_block_stat;
+pub mod sys_block_zram;
 
 pub use sys_block::*;
 pub use sys_block_stat::*;
+pub use sys_block_zram::*;
@@ -28,11 +28,11 @@ use std::ops::{Deref, DerefMut};
 use std::path::Path;
 
 use super::common::{
-    BLOCK_SIZE_DEFAULT, DEV, DEVICE_HWMON, DEVICE_MODEL, DEVICE_VENDOR, DM_NAME, HIDDEN, HOLDERS,
-    HWMON, LOOP_BACKING_FILE, PARTITION, QUEUE_ROTATIONAL, REMOVABLE, RO, SIZE, SLAVES, SYS_BLOCK,
-    TEMP1_HIGHEST, TEMP1_INPUT, TEMP1_LOWEST,
+    DEV, DEVICE_HWMON, DEVICE_MODEL, DEVICE_VENDOR, DM_NAME, HIDDEN, HOLDERS, HWMON,
+    LOOP_BACKING_FILE, PARTITION, QUEUE_ROTATIONAL, REMOVABLE, RO, SIZE, SLAVES, SYS_BLOCK,
+    TEMP1_HIGHEST, TEMP1_INPUT, TEMP1_LOWEST, ZRAM,
 };
-use crate::{bool_from_str, get_string_from_file, get_string_from_path, SysBlockStat};
+use crate::{bool_from_str, get_string_from_file, get_string_from_path, Bytes, SysBlockStat};
 
 /// Vec<BlockDeviceInfo>
 // TODO: tree like lsblk
@@ -65,6 +65,8 @@ impl SysBlockInfos {
                 .flatten()
                 .filter(|e| !e.file_name().to_string_lossy().starts_with("dm"))
                 .filter(|e| !e.file_name().to_string_lossy().starts_with("md"))
+                // compressed RAM, see `Zrams`
+                .filter(|e| !e.file_name().to_string_lossy().starts_with(ZRAM))
             {
                 // if !entry.file_name().to_string_lossy().starts_with("dm")
                 // && !entry.file_name().to_string_lossy().starts_with("md")
@@ -93,6 +95,25 @@ impl SysBlockInfos {
         Ok(block_info)
     }
 
+    /// find device in the tree, partitions and holders included, by device path
+    /// (e.g. `/dev/sda5`, `/dev/dm-1`, `/dev/mapper/vg-swap`)
+    pub fn find_by_path(&self, path: &str) -> Option<(&String, &SysBlockInfo)> {
+        for (name, device) in self.iter() {
+            let found = match path.strip_prefix("/dev/mapper/") {
+                Some(dm_name) => device.dm_name.as_deref() == Some(dm_name),
+                None => path.strip_prefix("/dev/") == Some(name.as_str()),
+            };
+            if found {
+                return Some((name, device));
+            }
+            if let Some(child) = device.holders.find_by_path(path) {
+                return Some(child);
+            }
+        }
+
+        None
+    }
+
     // pub fn diff(self, other: BlockDevicesInfo) -> Self {
     // let a = self.clone();
     // for (i, device) in self.devices.iter().enumerate() {
@@ -128,8 +149,8 @@ pub struct SysBlockInfo {
     pub removable: Option<bool>,
     /// `/sys/block/<DEVICE>/hidden`
     pub hidden: Option<bool>,
-    /// `/sys/block/<DEVICE>/size`
-    pub size: u64,
+    /// `/sys/block/<DEVICE>/size` (512 B sectors)
+    pub size: Bytes,
     /// `/sys/block/<DEVICE>/partition`
     pub partition: Option<u64>,
     /// `/sys/block/<DEVICE>/queue/rotational`
@@ -175,7 +196,7 @@ impl SysBlockInfo {
         // `/sys/block/*/size`
         let f = path.join(SIZE);
         if let Ok(size) = get_string_from_file(f) {
-            device.size = (size.parse().unwrap_or(0) * BLOCK_SIZE_DEFAULT) as u64;
+            device.size = Bytes::from_sectors(size.parse().unwrap_or(0));
         }
 
         // `/sys/block/*/device/model`
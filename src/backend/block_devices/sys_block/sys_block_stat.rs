@@ -1,5 +1,5 @@
 use super::common::{STAT, SYS_BLOCK};
-use crate::get_string_from_file;
+use crate::{get_string_from_file, Bytes};
 use std::io::{Error, ErrorKind};
 use std::ops::Sub;
 use std::path::Path;
@@ -107,6 +107,16 @@ impl SysBlockStat {
         }
     }
 
+    /// `read_sectors` in bytes
+    pub fn read_bytes(&self) -> Bytes {
+        Bytes::from_sectors(self.read_sectors)
+    }
+
+    /// `write_sectors` in bytes
+    pub fn write_bytes(&self) -> Bytes {
+        Bytes::from_sectors(self.write_sectors)
+    }
+
     // pub fn diff(self, other: SysBlockStat) -> Self {
     // self - other
     // }
@@ -123,8 +133,8 @@ fn get_block_device_stats() {
     let stats1 = SysBlockStat::get("sde/sde5").unwrap();
     // let diff = stats1.diff(stats0);
 
-    let r = ((stats1.read_sectors - stats0.read_sectors) * 512) / dt;
-    let w = ((stats1.write_sectors - stats0.write_sectors) * 512) / dt;
+    let r = (stats1.read_bytes() - stats0.read_bytes()).as_u64() / dt;
+    let w = (stats1.write_bytes() - stats0.write_bytes()).as_u64() / dt;
 
     let s = format!(
         "{} {}",
@@ -0,0 +1,214 @@
+/// get info from sysfs
+/// ```text
+/// /sys/block/zram*/disksize
+///                 /comp_algorithm
+///                 /mm_stat
+///                 /io_stat
+/// ```
+///
+/// INFO: [kernel.org](https://docs.kernel.org/admin-guide/blockdev/zram.html)
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::str::FromStr;
+
+use super::common::{
+    SYS_BLOCK, ZRAM, ZRAM_COMP_ALGORITHM, ZRAM_DISKSIZE, ZRAM_IO_STAT, ZRAM_MM_STAT,
+};
+use crate::{get_string_from_file, get_string_from_path, Bytes};
+
+// `mm_stat`, sizes in bytes, counters in pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ZramMmStat {
+    /// uncompressed size of data stored
+    pub orig_data_size: Bytes,
+    /// compressed size of data stored
+    pub compr_data_size: Bytes,
+    /// memory allocated for this disk, including allocator fragmentation and metadata overhead
+    pub mem_used_total: Bytes,
+    /// maximum amount of memory zram can use, `0` - no limit
+    pub mem_limit: Bytes,
+    /// maximum amount of memory zram has consumed
+    pub mem_used_max: Bytes,
+    /// number of same element filled pages written (e.g. zero pages), no memory allocated
+    pub same_pages: u64,
+    /// number of pages freed during compaction
+    pub pages_compacted: u64,
+    /// number of incompressible pages
+    pub huge_pages: u64,
+}
+
+impl ZramMmStat {
+    /// compression ratio (e.g. `3.2` - original data is 3.2 times larger than used memory)
+    pub fn get_ratio(&self) -> Option<f64> {
+        match self.mem_used_total.as_u64() {
+            0 => None,
+            used => Some(self.orig_data_size.as_f64() / used as f64),
+        }
+    }
+}
+
+impl FromStr for ZramMmStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v: Vec<u64> = s
+            .split_whitespace()
+            .map(|x| x.parse().unwrap_or_default())
+            .collect();
+
+        // NOTE: `huge_pages` since 4.19, `huge_pages_since` since 5.15
+        if v.len() < 7 {
+            return Err(Error::new(ErrorKind::InvalidData, "mm_stat"));
+        }
+
+        Ok(ZramMmStat {
+            orig_data_size: Bytes(v[0]),
+            compr_data_size: Bytes(v[1]),
+            mem_used_total: Bytes(v[2]),
+            mem_limit: Bytes(v[3]),
+            mem_used_max: Bytes(v[4]),
+            same_pages: v[5],
+            pages_compacted: v[6],
+            huge_pages: v.get(7).copied().unwrap_or_default(),
+        })
+    }
+}
+
+// `io_stat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ZramIoStat {
+    /// number of failed reads
+    pub failed_reads: u64,
+    /// number of failed writes
+    pub failed_writes: u64,
+    /// number of non-page-size-aligned I/O requests
+    pub invalid_io: u64,
+    /// number of freed slots notifications from the swap layer
+    pub notify_free: u64,
+}
+
+impl FromStr for ZramIoStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v: Vec<u64> = s
+            .split_whitespace()
+            .map(|x| x.parse().unwrap_or_default())
+            .collect();
+
+        if v.len() < 4 {
+            return Err(Error::new(ErrorKind::InvalidData, "io_stat"));
+        }
+
+        Ok(ZramIoStat {
+            failed_reads: v[0],
+            failed_writes: v[1],
+            invalid_io: v[2],
+            notify_free: v[3],
+        })
+    }
+}
+
+/// selected algorithm in brackets (e.g. `lzo [lzo-rle] lz4 zstd` -> `lzo-rle`)
+pub fn parse_selected_algorithm(s: &str) -> Option<String> {
+    s.split_whitespace()
+        .find_map(|a| a.strip_prefix('[').and_then(|a| a.strip_suffix(']')))
+        .map(|a| a.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Zram {
+    /// `disksize` maximum uncompressed size, `0` - device is not initialized
+    pub disksize: Bytes,
+    /// `comp_algorithm` selected compression algorithm (e.g. `lzo-rle`, `zstd`)
+    pub comp_algorithm: Option<String>,
+    /// `mm_stat`
+    pub mm_stat: Option<ZramMmStat>,
+    /// `io_stat`
+    pub io_stat: Option<ZramIoStat>,
+}
+
+impl Zram {
+    // get zram device by given path (e.g. `/sys/block/zram0`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        Ok(Zram {
+            disksize: Bytes(
+                get_string_from_file(path.join(ZRAM_DISKSIZE))?
+                    .parse()
+                    .unwrap_or_default(),
+            ),
+            comp_algorithm: get_string_from_path(path, ZRAM_COMP_ALGORITHM)
+                .and_then(|s| parse_selected_algorithm(&s)),
+            mm_stat: get_string_from_path(path, ZRAM_MM_STAT).and_then(|s| s.parse().ok()),
+            io_stat: get_string_from_path(path, ZRAM_IO_STAT).and_then(|s| s.parse().ok()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Zrams {
+    /// `BTreeMap<name, Zram{}>` (e.g. `zram0`)
+    pub devices: BTreeMap<String, Zram>,
+}
+
+impl Deref for Zrams {
+    type Target = BTreeMap<String, Zram>;
+
+    fn deref(&self) -> &BTreeMap<String, Zram> {
+        &self.devices
+    }
+}
+
+impl DerefMut for Zrams {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, Zram> {
+        &mut self.devices
+    }
+}
+
+impl Zrams {
+    pub fn get() -> Result<Self, Error> {
+        let mut zrams = Zrams::default();
+
+        if let Ok(dir) = std::fs::read_dir(SYS_BLOCK) {
+            for entry in dir
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().starts_with(ZRAM))
+            {
+                // NOTE: hot-removed or unreadable devices are skipped
+                if let Ok(zram) = Zram::get(entry.path()) {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    zrams.insert(name, zram);
+                }
+            }
+        }
+
+        Ok(zrams)
+    }
+}
+
+#[test]
+fn zram_from_str_test() {
+    let mm_stat: ZramMmStat =
+        "  4096000  1024000  1280000        0  1400000      100       10        3        0"
+            .parse()
+            .unwrap();
+    assert_eq!(mm_stat.orig_data_size, Bytes(4096000));
+    assert_eq!(mm_stat.compr_data_size, Bytes(1024000));
+    assert_eq!(mm_stat.same_pages, 100);
+    assert_eq!(mm_stat.huge_pages, 3);
+    assert_eq!(mm_stat.get_ratio(), Some(3.2));
+    assert_eq!(ZramMmStat::default().get_ratio(), None);
+
+    let io_stat: ZramIoStat = "        0        1 2        3".parse().unwrap();
+    assert_eq!(io_stat.notify_free, 3);
+
+    assert_eq!(
+        parse_selected_algorithm("lzo [lzo-rle] lz4 zstd"),
+        Some("lzo-rle".to_string())
+    );
+    assert_eq!(parse_selected_algorithm("lzo lz4"), None);
+}
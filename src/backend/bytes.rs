@@ -0,0 +1,92 @@
+/// byte quantity with unit conversions in one place
+///
+/// ```text
+/// /proc/meminfo                   kB (KiB, 1024 B)
+/// /sys/block/<DEVICE>/size, stat  sectors (512 B, independent of the device logical block size)
+/// /proc/<PID>/stat rss            pages (sysconf(3) `_SC_PAGESIZE`)
+/// statvfs(3)                      blocks (`f_frsize`)
+/// ```
+use std::ops::{Add, AddAssign, Sub};
+
+/// `kB` in procfs is KiB
+pub(crate) const KIB: u64 = 1024;
+/// sysfs block layer sector size
+pub(crate) const SECTOR_SIZE: u64 = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bytes(pub u64);
+
+impl Bytes {
+    /// from procfs `kB` values (e.g. `MemTotal: 6158152 kB`)
+    pub fn from_kib(n: u64) -> Self {
+        Bytes(n.saturating_mul(KIB))
+    }
+
+    /// from sysfs block layer 512 B sectors (e.g. `/sys/block/sda/size`)
+    pub fn from_sectors(n: u64) -> Self {
+        Bytes(n.saturating_mul(SECTOR_SIZE))
+    }
+
+    /// from memory pages, using the system page size
+    pub fn from_pages(n: u64) -> Self {
+        Bytes(n.saturating_mul(page_size()))
+    }
+
+    /// from filesystem blocks of given size (e.g. statvfs `f_blocks * f_frsize`)
+    pub fn from_blocks(n: u64, block_size: u64) -> Self {
+        Bytes(n.saturating_mul(block_size))
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64
+    }
+}
+
+/// memory page size in bytes (usually 4096)
+pub fn page_size() -> u64 {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 {
+        size as u64
+    } else {
+        4096
+    }
+}
+
+impl Add for Bytes {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Bytes(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl AddAssign for Bytes {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+// NOTE: saturating, counters of two snapshots can be reset (e.g. device re-attached)
+impl Sub for Bytes {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Bytes(self.0.saturating_sub(rhs.0))
+    }
+}
+
+#[test]
+fn bytes_test() {
+    assert_eq!(Bytes::from_kib(6158152), Bytes(6158152 * 1024));
+    assert_eq!(Bytes::from_sectors(2), Bytes(1024));
+    assert_eq!(Bytes::from_blocks(10, 4096), Bytes(40960));
+    assert_eq!(Bytes::from_pages(1), Bytes(page_size()));
+    assert_eq!(Bytes(5) - Bytes(10), Bytes(0));
+    assert_eq!(Bytes(5) + Bytes(10), Bytes(15));
+    assert_eq!(Bytes(u64::MAX) + Bytes(1), Bytes(u64::MAX));
+    assert_eq!(Bytes::from_kib(1).as_f64(), 1024.0);
+}
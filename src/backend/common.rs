@@ -32,3 +32,32 @@ pub fn option_bool_to_str(option: Option<bool>) -> String {
         None => " ",
     })
 }
+
+/// parse kernel cpu list format into cpu numbers (e.g. `0-3,8,10-11` -> `[0, 1, 2, 3, 8, 10, 11]`)
+pub fn parse_cpu_list(s: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+
+    for range in s.trim().split(',').filter(|r| !r.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = range.parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+
+    cpus
+}
+
+#[test]
+fn parse_cpu_list_test() {
+    assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+    assert_eq!(parse_cpu_list("0"), vec![0]);
+    assert!(parse_cpu_list("").is_empty());
+}
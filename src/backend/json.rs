@@ -0,0 +1,287 @@
+/// minimal JSON parser for small API responses (e.g. container engine sockets)
+/// and writer for reports (e.g. `--json`)
+///
+/// NOTE: numbers are `f64`, duplicate object keys are kept, first one wins on lookup
+/// INFO: [rfc8259](https://www.rfc-editor.org/rfc/rfc8259)
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Json {
+    #[default]
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// keys in document order
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// value of given object key, `None` for other types or missing keys
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Json::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// array items, empty for other types
+    pub fn as_array(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            _ => &[],
+        }
+    }
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("json: {}", message))
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect_literal(chars: &mut Peekable<Chars>, literal: &str, value: Json) -> Result<Json, Error> {
+    for expected in literal.chars() {
+        if chars.next() != Some(expected) {
+            return Err(invalid("invalid literal"));
+        }
+    }
+    Ok(value)
+}
+
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, Error> {
+    let mut s = String::new();
+
+    // opening quote checked by caller
+    chars.next();
+    loop {
+        match chars.next().ok_or_else(|| invalid("unterminated string"))? {
+            '"' => return Ok(s),
+            '\\' => match chars.next().ok_or_else(|| invalid("unterminated escape"))? {
+                '"' => s.push('"'),
+                '\\' => s.push('\\'),
+                '/' => s.push('/'),
+                'b' => s.push('\u{8}'),
+                'f' => s.push('\u{c}'),
+                'n' => s.push('\n'),
+                'r' => s.push('\r'),
+                't' => s.push('\t'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let code = u32::from_str_radix(&hex, 16).map_err(|_| invalid("invalid \\u"))?;
+                    // NOTE: surrogate pairs (e.g. emoji) are replaced
+                    s.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+                _ => return Err(invalid("invalid escape")),
+            },
+            c => s.push(c),
+        }
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Json, Error> {
+    skip_whitespace(chars);
+
+    match chars.peek().ok_or_else(|| invalid("unexpected end"))? {
+        'n' => expect_literal(chars, "null", Json::Null),
+        't' => expect_literal(chars, "true", Json::Bool(true)),
+        'f' => expect_literal(chars, "false", Json::Bool(false)),
+        '"' => Ok(Json::String(parse_string(chars)?)),
+        '[' => {
+            chars.next();
+            let mut items = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Ok(Json::Array(items));
+            }
+            loop {
+                items.push(parse_value(chars)?);
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some(']') => return Ok(Json::Array(items)),
+                    _ => return Err(invalid("expected `,` or `]`")),
+                }
+            }
+        }
+        '{' => {
+            chars.next();
+            let mut members = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Ok(Json::Object(members));
+            }
+            loop {
+                skip_whitespace(chars);
+                if chars.peek() != Some(&'"') {
+                    return Err(invalid("expected key"));
+                }
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                if chars.next() != Some(':') {
+                    return Err(invalid("expected `:`"));
+                }
+                members.push((key, parse_value(chars)?));
+                skip_whitespace(chars);
+                match chars.next() {
+                    Some(',') => continue,
+                    Some('}') => return Ok(Json::Object(members)),
+                    _ => return Err(invalid("expected `,` or `}`")),
+                }
+            }
+        }
+        _ => {
+            let mut number = String::new();
+            while let Some(c) =
+                chars.next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+            {
+                number.push(c);
+            }
+            number
+                .parse()
+                .map(Json::Number)
+                .map_err(|_| invalid("invalid value"))
+        }
+    }
+}
+
+impl FromStr for Json {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars().peekable();
+        let value = parse_value(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            None => Ok(value),
+            Some(_) => Err(invalid("trailing characters")),
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// compact JSON, non finite numbers as `null`
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{}", b),
+            Json::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Json::Number(_) => write!(f, "null"),
+            Json::String(s) => write_string(f, s),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[test]
+fn json_from_str_test() {
+    let s = r#"[{"Id":"8dfafdbc3a40","Names":["/web"],"Created":1700000000,
+        "Labels":{},"Ports":[],"Up":true,"Ref":null,"Note":"tab\tquote\" é"}]"#;
+    let json: Json = s.parse().unwrap();
+
+    let container = &json.as_array()[0];
+    assert_eq!(
+        container.get("Id").and_then(Json::as_str),
+        Some("8dfafdbc3a40")
+    );
+    assert_eq!(
+        container.get("Names").unwrap().as_array(),
+        &[Json::String("/web".to_string())]
+    );
+    assert_eq!(
+        container.get("Created").and_then(Json::as_f64),
+        Some(1700000000.0)
+    );
+    assert_eq!(container.get("Labels"), Some(&Json::Object(vec![])));
+    assert_eq!(container.get("Up"), Some(&Json::Bool(true)));
+    assert_eq!(container.get("Ref"), Some(&Json::Null));
+    assert_eq!(
+        container.get("Note").and_then(Json::as_str),
+        Some("tab\tquote\" é")
+    );
+    assert_eq!(container.get("Missing"), None);
+
+    assert_eq!("-1.5e3".parse::<Json>().unwrap(), Json::Number(-1500.0));
+    assert!("[1, 2".parse::<Json>().is_err());
+    assert!("{\"a\" 1}".parse::<Json>().is_err());
+    assert!("nul".parse::<Json>().is_err());
+    assert!("1 2".parse::<Json>().is_err());
+}
+
+#[test]
+fn json_to_string_test() {
+    let json = Json::Object(vec![
+        (
+            "name".to_string(),
+            Json::String("a\"b\\c\n\u{1}é".to_string()),
+        ),
+        (
+            "values".to_string(),
+            Json::Array(vec![Json::Number(1.5), Json::Bool(false), Json::Null]),
+        ),
+        ("nan".to_string(), Json::Number(f64::NAN)),
+    ]);
+    let s = json.to_string();
+    assert_eq!(
+        s,
+        r#"{"name":"a\"b\\c\n\u0001é","values":[1.5,false,null],"nan":null}"#
+    );
+    assert_eq!(s.parse::<Json>().unwrap().get("values"), json.get("values"));
+}
@@ -1,13 +1,17 @@
 pub mod block_devices;
+pub mod bytes;
 pub mod common;
 pub mod date;
 pub mod hwmon;
+pub mod json;
 pub mod network;
 pub mod system;
 
 pub use block_devices::*;
+pub use bytes::*;
 pub use common::*;
 pub use date::*;
 pub use hwmon::*;
+pub use json::*;
 pub use network::*;
 pub use system::*;
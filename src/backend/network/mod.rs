@@ -1,7 +1,9 @@
 pub mod proc_net_dev;
+pub mod proc_net_socket;
 // pub mod sys_class_net;
 pub mod libc_networks;
 
 pub use proc_net_dev::*;
+pub use proc_net_socket::*;
 // pub use sys_class_net::*;
 pub use libc_networks::*;
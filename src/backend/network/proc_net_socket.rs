@@ -0,0 +1,373 @@
+/// get info from procfs
+/// ```text
+/// /proc/net/tcp
+/// /proc/net/tcp6
+/// /proc/net/udp
+/// /proc/net/udp6
+/// /proc/net/unix
+/// /proc/<PID>/fd/*    `socket:[<INODE>]` links, owner of a socket
+/// ```
+///
+/// sockets of the network namespace of the reading process
+/// NOTE: fds of other users' processes are readable by root only, their sockets have no owner
+/// INFO: [kernel.org](https://docs.kernel.org/networking/proc_net_tcp.html)
+use std::collections::BTreeMap;
+use std::fs::{read_dir, read_link, read_to_string};
+use std::io::{Error, ErrorKind};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::{Deref, DerefMut};
+
+use crate::PROC;
+
+/// `/proc/net/tcp`
+pub const PROC_NET_TCP: &str = "/proc/net/tcp";
+/// `/proc/net/tcp6`
+pub const PROC_NET_TCP6: &str = "/proc/net/tcp6";
+/// `/proc/net/udp`
+pub const PROC_NET_UDP: &str = "/proc/net/udp";
+/// `/proc/net/udp6`
+pub const PROC_NET_UDP6: &str = "/proc/net/udp6";
+/// `/proc/net/unix`
+pub const PROC_NET_UNIX: &str = "/proc/net/unix";
+
+/// `st` of `TCP_ESTABLISHED` (`include/net/tcp_states.h`)
+pub(crate) const TCP_ESTABLISHED: u8 = 0x01;
+/// `st` of `TCP_LISTEN`
+pub(crate) const TCP_LISTEN: u8 = 0x0a;
+/// `Flags` of listening unix sockets (`__SO_ACCEPTCON`)
+pub(crate) const UNIX_ACCEPTCON: u32 = 0x0001_0000;
+/// `St` of connected unix sockets (`SS_CONNECTED`)
+pub(crate) const UNIX_CONNECTED: u8 = 0x03;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum SocketProtocol {
+    #[default]
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SocketProtocol::Tcp => "tcp",
+            SocketProtocol::Tcp6 => "tcp6",
+            SocketProtocol::Udp => "udp",
+            SocketProtocol::Udp6 => "udp6",
+            SocketProtocol::Unix => "unix",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NetSocket {
+    pub protocol: SocketProtocol,
+    /// `None` for unix sockets
+    pub local_address: Option<IpAddr>,
+    pub local_port: u16,
+    /// `None` for unix sockets
+    pub remote_address: Option<IpAddr>,
+    pub remote_port: u16,
+    /// `st` (e.g. `0A` TCP listen), unix `St` (e.g. `03` connected)
+    pub state: u8,
+    /// `uid`, `0` for unix sockets (not in `/proc/net/unix`)
+    pub uid: u32,
+    pub inode: u64,
+    /// unix socket path (e.g. `/run/dbus/system_bus_socket`, `@/tmp/.X11-unix/X0` abstract)
+    pub path: Option<String>,
+    /// TCP listen, unconnected bound UDP, unix `__SO_ACCEPTCON`
+    pub listening: bool,
+}
+
+impl NetSocket {
+    /// listener the socket is a connection of (same protocol, local port and address, or wildcard)
+    /// NOTE: IPv4 connections of a dual-stack `[::]` listener are in `tcp6` (v4-mapped)
+    pub fn is_connection_of(&self, listener: &NetSocket) -> bool {
+        match self.protocol {
+            SocketProtocol::Unix => {
+                self.state == UNIX_CONNECTED && !self.listening && self.path == listener.path
+            }
+            SocketProtocol::Tcp | SocketProtocol::Tcp6 => {
+                self.state == TCP_ESTABLISHED
+                    && self.protocol == listener.protocol
+                    && self.local_port == listener.local_port
+                    && (self.local_address == listener.local_address
+                        || listener.local_address.is_some_and(|a| a.is_unspecified()))
+            }
+            SocketProtocol::Udp | SocketProtocol::Udp6 => false,
+        }
+    }
+
+    /// local address and port (e.g. `127.0.0.1:22`, `[::]:80`) or unix path
+    pub fn get_local(&self) -> String {
+        match (self.local_address, &self.path) {
+            (Some(IpAddr::V4(address)), _) => format!("{}:{}", address, self.local_port),
+            (Some(IpAddr::V6(address)), _) => format!("[{}]:{}", address, self.local_port),
+            (None, Some(path)) => path.clone(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// parse `/proc/net/{tcp,udp}` address (e.g. `0100007F:0016` -> `127.0.0.1`, `22`)
+/// NOTE: address is printed as 32 bit words in host byte order, port in hex
+pub fn parse_socket_address(s: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = s.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let mut bytes = Vec::new();
+    for i in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    let address = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => return None,
+    };
+
+    Some((address, port))
+}
+
+/// parse `/proc/net/{tcp,tcp6,udp,udp6}`
+/// (e.g. `0: 0100007F:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000 0 0 1234 ...`)
+pub fn parse_proc_net_ip(s: &str, protocol: SocketProtocol) -> Result<Vec<NetSocket>, Error> {
+    let mut sockets = Vec::new();
+
+    for line in s.lines().skip(1) {
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.len() < 10 {
+            return Err(Error::new(ErrorKind::InvalidData, "proc net: short line"));
+        }
+        let invalid = || Error::new(ErrorKind::InvalidData, "proc net: invalid address");
+        let (local_address, local_port) = parse_socket_address(v[1]).ok_or_else(invalid)?;
+        let (remote_address, remote_port) = parse_socket_address(v[2]).ok_or_else(invalid)?;
+        let state = u8::from_str_radix(v[3], 16).unwrap_or_default();
+
+        let listening = match protocol {
+            SocketProtocol::Tcp | SocketProtocol::Tcp6 => state == TCP_LISTEN,
+            // bound, not connected
+            _ => remote_port == 0,
+        };
+
+        sockets.push(NetSocket {
+            protocol,
+            local_address: Some(local_address),
+            local_port,
+            remote_address: Some(remote_address),
+            remote_port,
+            state,
+            uid: v[7].parse().unwrap_or_default(),
+            inode: v[9].parse().unwrap_or_default(),
+            path: None,
+            listening,
+        });
+    }
+
+    Ok(sockets)
+}
+
+/// parse `/proc/net/unix`
+/// (e.g. `0000000000000000: 00000002 00000000 00010000 0001 01 12345 /run/dbus/system_bus_socket`)
+pub fn parse_proc_net_unix(s: &str) -> Result<Vec<NetSocket>, Error> {
+    let mut sockets = Vec::new();
+
+    for line in s.lines().skip(1) {
+        let v: Vec<&str> = line.split_whitespace().collect();
+        if v.len() < 7 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "proc net unix: short line",
+            ));
+        }
+        let flags = u32::from_str_radix(v[3], 16).unwrap_or_default();
+
+        sockets.push(NetSocket {
+            protocol: SocketProtocol::Unix,
+            state: u8::from_str_radix(v[5], 16).unwrap_or_default(),
+            inode: v[6].parse().unwrap_or_default(),
+            // NOTE: paths with spaces are printed as is
+            path: v.get(7).map(|_| v[7..].join(" ")),
+            listening: flags & UNIX_ACCEPTCON != 0,
+            ..Default::default()
+        });
+    }
+
+    Ok(sockets)
+}
+
+/// socket owners (`BTreeMap<inode, pid>`) from `/proc/<PID>/fd/*` links (e.g. `socket:[1234]`)
+/// NOTE: shared sockets (e.g. after fork) belong to the lowest pid
+pub fn get_socket_owners() -> BTreeMap<u64, u32> {
+    let mut owners = BTreeMap::new();
+
+    let Ok(dir) = read_dir(PROC) else {
+        return owners;
+    };
+    for entry in dir.flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else {
+            continue;
+        };
+        // NOTE: not readable for other users' processes without root
+        let Ok(fds) = read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(link) = read_link(fd.path()) else {
+                continue;
+            };
+            let link = link.to_string_lossy();
+            if let Some(inode) = link
+                .strip_prefix("socket:[")
+                .and_then(|s| s.strip_suffix(']'))
+                .and_then(|s| s.parse().ok())
+            {
+                owners
+                    .entry(inode)
+                    .and_modify(|owner: &mut u32| *owner = (*owner).min(pid))
+                    .or_insert(pid);
+            }
+        }
+    }
+
+    owners
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NetSockets {
+    pub sockets: Vec<NetSocket>,
+}
+
+impl Deref for NetSockets {
+    type Target = Vec<NetSocket>;
+
+    fn deref(&self) -> &Vec<NetSocket> {
+        &self.sockets
+    }
+}
+
+impl DerefMut for NetSockets {
+    fn deref_mut(&mut self) -> &mut Vec<NetSocket> {
+        &mut self.sockets
+    }
+}
+
+impl NetSockets {
+    pub fn get() -> Result<Self, Error> {
+        let mut sockets = NetSockets::default();
+
+        let files = [
+            (PROC_NET_TCP, SocketProtocol::Tcp),
+            (PROC_NET_TCP6, SocketProtocol::Tcp6),
+            (PROC_NET_UDP, SocketProtocol::Udp),
+            (PROC_NET_UDP6, SocketProtocol::Udp6),
+        ];
+        for (file, protocol) in files {
+            // NOTE: `*6` files do not exist with IPv6 disabled
+            if let Ok(buf) = read_to_string(file) {
+                sockets.extend(parse_proc_net_ip(&buf, protocol)?);
+            }
+        }
+        if let Ok(buf) = read_to_string(PROC_NET_UNIX) {
+            sockets.extend(parse_proc_net_unix(&buf)?);
+        }
+
+        Ok(sockets)
+    }
+
+    /// listening sockets with number of connections, sorted by protocol and port
+    pub fn get_listeners(&self) -> Vec<(&NetSocket, usize)> {
+        let mut listeners: Vec<(&NetSocket, usize)> = self
+            .iter()
+            .filter(|socket| socket.listening)
+            .map(|listener| {
+                let connections = self
+                    .iter()
+                    .filter(|socket| socket.is_connection_of(listener))
+                    .count();
+                (listener, connections)
+            })
+            .collect();
+        listeners.sort_by(|(a, _), (b, _)| {
+            (a.protocol, a.local_port, &a.path).cmp(&(b.protocol, b.local_port, &b.path))
+        });
+        listeners
+    }
+}
+
+#[test]
+fn parse_proc_net_test() {
+    let s = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21000 1 0000000000000000 100 0 0 10 0
+   1: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 22000 1 0000000000000000 100 0 0 10 0
+   2: 0F02000A:0016 0202000A:C5D4 01 00000000:00000000 02:00090A2B 00000000     0        0 23000 4 0000000000000000 20 4 30 10 -1
+   3: 0100007F:0277 0100007F:D2F0 06 00000000:00000000 03:00000F71 00000000     0        0 0 3 0000000000000000
+";
+    let sockets = NetSockets {
+        sockets: parse_proc_net_ip(s, SocketProtocol::Tcp).unwrap(),
+    };
+    assert_eq!(sockets.len(), 4);
+    assert_eq!(sockets[0].get_local(), "127.0.0.1:631");
+    assert_eq!(sockets[0].inode, 21000);
+    assert!(sockets[0].listening);
+    assert_eq!(
+        sockets[2].remote_address,
+        Some(IpAddr::V4(Ipv4Addr::new(10, 0, 2, 2)))
+    );
+
+    // ssh on all addresses with one connection, cups without (TIME_WAIT)
+    let listeners = sockets.get_listeners();
+    assert_eq!(listeners.len(), 2);
+    assert_eq!(listeners[0].0.get_local(), "0.0.0.0:22");
+    assert_eq!(listeners[0].1, 1);
+    assert_eq!(listeners[1].1, 0);
+
+    let s = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0277 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 24000 1 0000000000000000 100 0 0 10 0
+";
+    let sockets = parse_proc_net_ip(s, SocketProtocol::Tcp6).unwrap();
+    assert_eq!(sockets[0].get_local(), "[::1]:631");
+
+    let s = "Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 25000 /run/dbus/system_bus_socket
+0000000000000000: 00000003 00000000 00000000 0001 03 25001 /run/dbus/system_bus_socket
+0000000000000000: 00000003 00000000 00000000 0001 03 25002
+0000000000000000: 00000002 00000000 00010000 0001 01 25003 @/tmp/.X11-unix/X0
+";
+    let sockets = NetSockets {
+        sockets: parse_proc_net_unix(s).unwrap(),
+    };
+    let listeners = sockets.get_listeners();
+    assert_eq!(listeners.len(), 2);
+    assert_eq!(listeners[0].0.get_local(), "/run/dbus/system_bus_socket");
+    assert_eq!(listeners[0].1, 1);
+    assert_eq!(listeners[1].0.get_local(), "@/tmp/.X11-unix/X0");
+
+    assert!(parse_socket_address("0100007F").is_none());
+}
+
+#[test]
+fn listeners_dual_stack_test() {
+    // sshd on `0.0.0.0:22` and `[::]:22`, one IPv4 and one IPv6 connection
+    let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 22000 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:0016 0202000A:C5D4 01 00000000:00000000 02:00090A2B 00000000     0        0 23000 4 0000000000000000 20 4 30 10 -1
+";
+    let tcp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 22001 1 0000000000000000 100 0 0 10 0
+   1: 000080FE00000000FF1102A0FE6A89B2:0016 000080FE00000000FF1102A0FE1234AB:D2F0 01 00000000:00000000 02:00090A2B 00000000     0        0 23001 4 0000000000000000 20 4 30 10 -1
+";
+    let mut sockets = parse_proc_net_ip(tcp, SocketProtocol::Tcp).unwrap();
+    sockets.extend(parse_proc_net_ip(tcp6, SocketProtocol::Tcp6).unwrap());
+    let sockets = NetSockets { sockets };
+
+    let listeners = sockets.get_listeners();
+    assert_eq!(listeners.len(), 2);
+    assert_eq!(listeners[0].0.get_local(), "0.0.0.0:22");
+    assert_eq!(listeners[0].1, 1);
+    assert_eq!(listeners[1].0.get_local(), "[::]:22");
+    assert_eq!(listeners[1].1, 1);
+}
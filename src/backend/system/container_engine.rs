@@ -0,0 +1,402 @@
+/// get running containers from the Docker / Podman HTTP API over the local Unix socket
+/// ```text
+/// /var/run/docker.sock                    Docker
+/// /run/podman/podman.sock                 Podman (root)
+/// $XDG_RUNTIME_DIR/docker.sock            Docker (rootless)
+/// $XDG_RUNTIME_DIR/podman/podman.sock     Podman (rootless)
+///
+/// GET /containers/json                    running containers
+/// GET /containers/<ID>/json               `State.Pid`, `State.StartedAt`
+///
+/// /proc/<PID>/cgroup                      cgroup of the container
+/// /proc/<PID>/root/sys/class/net/eth0/iflink
+/// /sys/class/net/<INTERFACE>/ifindex      host side veth interface
+/// ```
+///
+/// NOTE: optional, engines without socket are skipped, sockets are usually root / `docker` group only
+/// NOTE: cgroups are world-readable, veth of containers is found with root only (`/proc/<PID>/root`)
+/// INFO: [docker.com](https://docs.docker.com/engine/api/)
+use std::fs::read_dir;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::DateTime;
+
+use crate::{
+    get_string_from_file, get_string_from_path, parse_proc_self_cgroup, Cgroup, Json, ProcNetDevs,
+    SYS_FS_CGROUP,
+};
+
+/// `/var/run/docker.sock`
+pub const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+/// `/run/podman/podman.sock`
+pub const PODMAN_SOCKET: &str = "/run/podman/podman.sock";
+/// `$XDG_RUNTIME_DIR/docker.sock`
+pub(crate) const DOCKER_ROOTLESS_SOCKET: &str = "docker.sock";
+/// `$XDG_RUNTIME_DIR/podman/podman.sock`
+pub(crate) const PODMAN_ROOTLESS_SOCKET: &str = "podman/podman.sock";
+/// `GET /containers/json`, running containers only
+pub(crate) const API_CONTAINERS: &str = "/containers/json";
+/// `/proc/<PID>/root/sys/class/net/eth0/iflink`, ifindex of the peer (host side veth)
+pub(crate) const CONTAINER_ETH0_IFLINK: &str = "root/sys/class/net/eth0/iflink";
+/// `/sys/class/net`
+pub(crate) const SYS_CLASS_NET: &str = "/sys/class/net";
+/// `/sys/class/net/<INTERFACE>/ifindex`
+pub(crate) const NET_IFINDEX: &str = "ifindex";
+
+/// timeout of socket reads and writes, a hanging engine must not block the update
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// decode `Transfer-Encoding: chunked` body (e.g. `5\r\nhello\r\n0\r\n\r\n`)
+pub fn decode_chunked(mut body: &[u8]) -> Result<Vec<u8>, Error> {
+    let invalid = || Error::new(ErrorKind::InvalidData, "http: invalid chunk");
+    let mut decoded = Vec::new();
+
+    loop {
+        let end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(invalid)?;
+        // chunk size in hex, extensions ignored (e.g. `1a;name=value`)
+        let size = String::from_utf8_lossy(&body[..end]);
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid())?;
+        body = &body[end + 2..];
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size).ok_or_else(invalid)?);
+        body = body.get(size + 2..).ok_or_else(invalid)?;
+    }
+}
+
+/// body of a HTTP/1.x response, status other than `200` is an error
+pub fn parse_http_response(response: &[u8]) -> Result<String, Error> {
+    let end = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "http: no header"))?;
+    let header = String::from_utf8_lossy(&response[..end]);
+    let body = &response[end + 4..];
+
+    // `HTTP/1.1 200 OK`
+    let status = header.lines().next().unwrap_or_default();
+    if status.split_whitespace().nth(1) != Some("200") {
+        return Err(Error::other(format!(
+            "http: unexpected status `{}`",
+            status
+        )));
+    }
+
+    let chunked = header.lines().any(|line| {
+        line.split_once(':').is_some_and(|(key, value)| {
+            key.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = match chunked {
+        true => decode_chunked(body)?,
+        false => body.to_vec(),
+    };
+
+    Ok(String::from_utf8_lossy(&body).to_string())
+}
+
+/// `GET` request over a Unix socket, body of the response as JSON
+pub fn http_get_unix<P: AsRef<Path>>(socket: P, path: &str) -> Result<Json, Error> {
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(SOCKET_TIMEOUT))?;
+    stream.set_write_timeout(Some(SOCKET_TIMEOUT))?;
+
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        path
+    )?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    parse_http_response(&response)?.parse()
+}
+
+/// host side veth of the container network namespace, by `iflink` of `eth0` (e.g. `veth1a2b3c4`)
+pub fn get_host_veth(pid: u32) -> Option<String> {
+    let iflink = get_string_from_file(
+        Path::new("/proc")
+            .join(pid.to_string())
+            .join(CONTAINER_ETH0_IFLINK),
+    )
+    .ok()?;
+
+    read_dir(SYS_CLASS_NET)
+        .ok()?
+        .flatten()
+        .find(|entry| get_string_from_path(&entry.path(), NET_IFINDEX).as_deref() == Some(&iflink))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EngineContainer {
+    /// `Docker` or `Podman`
+    pub engine: String,
+    /// `Id` (e.g. `8dfafdbc3a40...`)
+    pub id: String,
+    /// `Names` first name without `/` (e.g. `web`)
+    pub name: String,
+    /// `Image` (e.g. `nginx:latest`)
+    pub image: String,
+    /// `State` (e.g. `running`, `paused`)
+    pub state: String,
+    /// `State.StartedAt` as unix time
+    pub started_at: Option<i64>,
+    /// `State.Pid` main process on the host
+    pub pid: Option<u32>,
+    /// cgroup of the main process
+    pub cgroup: Option<Cgroup>,
+    /// host side veth interface (e.g. `veth1a2b3c4`)
+    pub veth: Option<String>,
+    /// `(rx, tx)` bytes of the host side veth, container receives host tx
+    pub veth_bytes: Option<(u64, u64)>,
+}
+
+impl EngineContainer {
+    /// containers from `GET /containers/json`
+    pub fn parse_list(json: &Json, engine: &str) -> Vec<EngineContainer> {
+        json.as_array()
+            .iter()
+            .map(|container| {
+                let string = |key: &str| {
+                    container
+                        .get(key)
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                EngineContainer {
+                    engine: engine.to_string(),
+                    id: string("Id"),
+                    name: container
+                        .get("Names")
+                        .and_then(|names| names.as_array().first())
+                        .and_then(Json::as_str)
+                        .unwrap_or_default()
+                        .trim_start_matches('/')
+                        .to_string(),
+                    image: string("Image"),
+                    state: string("State"),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    /// add `State.Pid` and `State.StartedAt` of `GET /containers/<ID>/json`
+    pub fn parse_inspect(&mut self, json: &Json) {
+        let Some(state) = json.get("State") else {
+            return;
+        };
+
+        // `0` if not running
+        self.pid = state
+            .get("Pid")
+            .and_then(Json::as_f64)
+            .filter(|pid| *pid > 0.0)
+            .map(|pid| pid as u32);
+        // e.g. `2024-01-02T10:20:30.123456789Z`
+        self.started_at = state
+            .get("StartedAt")
+            .and_then(Json::as_str)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|t| t.timestamp());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EngineContainers {
+    pub containers: Vec<EngineContainer>,
+    /// sockets exist, but are not accessible (e.g. not in `docker` group)
+    pub permission_denied: bool,
+}
+
+impl Deref for EngineContainers {
+    type Target = Vec<EngineContainer>;
+
+    fn deref(&self) -> &Vec<EngineContainer> {
+        &self.containers
+    }
+}
+
+impl DerefMut for EngineContainers {
+    fn deref_mut(&mut self) -> &mut Vec<EngineContainer> {
+        &mut self.containers
+    }
+}
+
+impl EngineContainers {
+    /// engine name and socket path of all known engines
+    pub fn get_sockets() -> Vec<(&'static str, PathBuf)> {
+        let mut sockets = vec![
+            ("Docker", PathBuf::from(DOCKER_SOCKET)),
+            ("Podman", PathBuf::from(PODMAN_SOCKET)),
+        ];
+        if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+            let runtime_dir = PathBuf::from(runtime_dir);
+            sockets.push(("Docker", runtime_dir.join(DOCKER_ROOTLESS_SOCKET)));
+            sockets.push(("Podman", runtime_dir.join(PODMAN_ROOTLESS_SOCKET)));
+        }
+        sockets
+    }
+
+    pub fn get() -> Result<Self, Error> {
+        let mut containers = EngineContainers::default();
+
+        for (engine, socket) in EngineContainers::get_sockets() {
+            if !socket.exists() {
+                continue;
+            }
+            match EngineContainers::get_from_socket(engine, &socket) {
+                Ok(engine_containers) => containers.extend(engine_containers.containers),
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                    containers.permission_denied = true
+                }
+                // NOTE: stale socket of a stopped engine, not an error
+                Err(_) => {}
+            }
+        }
+
+        // host side of the container, veth root only
+        let net = ProcNetDevs::get()?;
+        for container in containers.iter_mut() {
+            let Some(pid) = container.pid else {
+                continue;
+            };
+            container.cgroup = get_string_from_file(format!("/proc/{}/cgroup", pid))
+                .ok()
+                .and_then(|s| parse_proc_self_cgroup(&s))
+                .and_then(|path| Cgroup::get(SYS_FS_CGROUP, path.trim_start_matches('/')).ok());
+            container.veth = get_host_veth(pid);
+            container.veth_bytes = container
+                .veth
+                .as_ref()
+                .and_then(|veth| net.get(veth))
+                .map(|dev| (dev.rx_bytes, dev.tx_bytes));
+        }
+
+        Ok(containers)
+    }
+
+    /// running containers of one engine, without host side info
+    pub fn get_from_socket<P: AsRef<Path>>(engine: &str, socket: P) -> Result<Self, Error> {
+        let socket = socket.as_ref();
+        let list = http_get_unix(socket, API_CONTAINERS)?;
+
+        let mut containers = EngineContainer::parse_list(&list, engine);
+        for container in containers.iter_mut() {
+            // NOTE: container can be removed between requests
+            if let Ok(inspect) =
+                http_get_unix(socket, &format!("/containers/{}/json", container.id))
+            {
+                container.parse_inspect(&inspect);
+            }
+        }
+
+        Ok(EngineContainers {
+            containers,
+            permission_denied: false,
+        })
+    }
+}
+
+#[test]
+fn parse_http_response_test() {
+    let response = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\r\n[]";
+    assert_eq!(parse_http_response(response).unwrap(), "[]");
+
+    let response = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n[{}]\r\n2;x=y\r\n\r\n\r\n0\r\n\r\n";
+    assert_eq!(parse_http_response(response).unwrap(), "[{}]\r\n");
+
+    let response = b"HTTP/1.1 404 Not Found\r\n\r\n{\"message\":\"page not found\"}";
+    assert!(parse_http_response(response).is_err());
+    assert!(parse_http_response(b"HTTP/1.1 200 OK\r\n").is_err());
+    assert!(decode_chunked(b"zz\r\n").is_err());
+}
+
+#[test]
+fn engine_containers_get_from_socket_test() {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    // removed on every exit, failed asserts too
+    struct Socket(PathBuf);
+    impl Drop for Socket {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    // NOTE: unique per process, test runs can overlap
+    let socket = Socket(std::env::temp_dir().join(format!(
+        "termsysmon_engine_test_{}.sock",
+        std::process::id()
+    )));
+    let _ = std::fs::remove_file(&socket.0);
+    let listener = UnixListener::bind(&socket.0).unwrap();
+
+    // stub engine, one request per connection
+    let server = thread::spawn(move || {
+        for stream in listener.incoming().take(3) {
+            let mut stream = stream.unwrap();
+            let mut request_line = String::new();
+            BufReader::new(&stream)
+                .read_line(&mut request_line)
+                .unwrap();
+
+            let body = match request_line.split_whitespace().nth(1).unwrap_or_default() {
+                API_CONTAINERS => {
+                    r#"[{"Id":"8dfa","Names":["/web"],"Image":"nginx:latest","State":"running"},
+                    {"Id":"c0ff","Names":["/db"],"Image":"postgres:16","State":"running"}]"#
+                }
+                "/containers/8dfa/json" => {
+                    r#"{"Id":"8dfa","State":{"Status":"running","Pid":4242,"StartedAt":"2024-01-02T10:20:30.123456789Z"}}"#
+                }
+                _ => r#"{"message":"No such container"}"#,
+            };
+            let status = match body.contains("No such container") {
+                true => "404 Not Found",
+                false => "200 OK",
+            };
+            // chunked like the Docker daemon
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+
+    let containers = EngineContainers::get_from_socket("Docker", &socket.0).unwrap();
+    server.join().unwrap();
+
+    assert_eq!(containers.len(), 2);
+    let web = &containers[0];
+    assert_eq!(web.engine, "Docker");
+    assert_eq!(web.name, "web");
+    assert_eq!(web.image, "nginx:latest");
+    assert_eq!(web.state, "running");
+    assert_eq!(web.pid, Some(4242));
+    assert_eq!(web.started_at, Some(1704190830));
+    // inspect failed, list entry is kept
+    assert_eq!(containers[1].name, "db");
+    assert_eq!(containers[1].pid, None);
+
+    assert!(EngineContainers::get_from_socket("Docker", "/nonexistent.sock").is_err());
+}
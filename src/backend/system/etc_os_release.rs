@@ -0,0 +1,173 @@
+/// get info from
+/// ```text
+/// /etc/os-release
+/// /usr/lib/os-release (fallback)
+/// ```
+///
+/// newline separated `KEY=value` assignments, values quoted like in shell (e.g. `NAME="Arch Linux"`)
+/// INFO: [freedesktop.org](https://www.freedesktop.org/software/systemd/man/latest/os-release.html)
+use std::io::Error;
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const ETC_OS_RELEASE: &str = "/etc/os-release";
+pub const USR_LIB_OS_RELEASE: &str = "/usr/lib/os-release";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OsRelease {
+    /// `NAME` (e.g. `Debian GNU/Linux`)
+    pub name: Option<String>,
+    /// `PRETTY_NAME` (e.g. `Debian GNU/Linux 12 (bookworm)`)
+    pub pretty_name: Option<String>,
+    /// `VERSION` (e.g. `12 (bookworm)`)
+    pub version: Option<String>,
+    /// `VERSION_ID` (e.g. `12`)
+    pub version_id: Option<String>,
+    /// `ID` lower case distribution id (e.g. `debian`, `arch`)
+    pub id: Option<String>,
+    /// `ID_LIKE` space separated related distributions (e.g. `rhel fedora`)
+    pub id_like: Vec<String>,
+    /// `VERSION_CODENAME` (e.g. `bookworm`)
+    pub version_codename: Option<String>,
+    /// `BUILD_ID` rolling releases (e.g. `rolling`, `20240101.0.1`)
+    pub build_id: Option<String>,
+    /// `VARIANT` (e.g. `Workstation Edition`)
+    pub variant: Option<String>,
+}
+
+/// unquote shell-like value, escapes (`\"`, `\\`, `\$`, `` \` ``) in double quotes only
+pub fn unquote_os_release_value(s: &str) -> String {
+    let s = s.trim();
+
+    if let Some(inner) = s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        return inner.to_string();
+    }
+
+    let inner = s
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or(s);
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(next @ ('"' | '\\' | '$' | '`')) => value.push(next),
+                Some(next) => {
+                    value.push(c);
+                    value.push(next);
+                }
+                None => value.push(c),
+            },
+            _ => value.push(c),
+        }
+    }
+
+    value
+}
+
+impl OsRelease {
+    // NOTE: `/usr/lib/os-release` is used, if `/etc/os-release` does not exist
+    pub fn get() -> Result<Self, Error> {
+        let buf = get_string_from_file(ETC_OS_RELEASE)
+            .or_else(|_| get_string_from_file(USR_LIB_OS_RELEASE))?;
+        buf.parse()
+    }
+
+    /// distribution name to display (e.g. `Debian GNU/Linux 12 (bookworm)`)
+    pub fn get_display_name(&self) -> String {
+        if let Some(pretty_name) = &self.pretty_name {
+            return pretty_name.clone();
+        }
+
+        [&self.name, &self.version, &self.build_id]
+            .iter()
+            .filter_map(|s| s.as_deref())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// `ID` and `ID_LIKE`, most specific first
+    pub fn get_ids(&self) -> Vec<&str> {
+        self.id
+            .iter()
+            .chain(self.id_like.iter())
+            .map(|s| s.as_str())
+            .collect()
+    }
+}
+
+impl FromStr for OsRelease {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut os_release = OsRelease::default();
+
+        for line in s.lines().map(|line| line.trim()) {
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = unquote_os_release_value(value);
+            if value.is_empty() {
+                continue;
+            }
+
+            match key.trim() {
+                "NAME" => os_release.name = Some(value),
+                "PRETTY_NAME" => os_release.pretty_name = Some(value),
+                "VERSION" => os_release.version = Some(value),
+                "VERSION_ID" => os_release.version_id = Some(value),
+                "ID" => os_release.id = Some(value),
+                "ID_LIKE" => {
+                    os_release.id_like = value.split_whitespace().map(String::from).collect()
+                }
+                "VERSION_CODENAME" => os_release.version_codename = Some(value),
+                "BUILD_ID" => os_release.build_id = Some(value),
+                "VARIANT" => os_release.variant = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(os_release)
+    }
+}
+
+#[test]
+fn os_release_from_str_test() {
+    let s = r#"# comment
+NAME="Fedora Linux"
+VERSION="39 (Workstation Edition)"
+ID=fedora
+ID_LIKE='rhel centos'
+VERSION_ID=39
+VERSION_CODENAME=""
+PRETTY_NAME="Fedora Linux 39 (Workstation Edition)"
+VARIANT="Workstation \"Edition\" \\ \$HOME"
+BUILD_ID=rolling
+"#;
+    let os_release: OsRelease = s.parse().unwrap();
+    assert_eq!(os_release.name.as_deref(), Some("Fedora Linux"));
+    assert_eq!(os_release.id.as_deref(), Some("fedora"));
+    assert_eq!(os_release.id_like, vec!["rhel", "centos"]);
+    assert_eq!(os_release.version_id.as_deref(), Some("39"));
+    assert_eq!(os_release.version_codename, None);
+    assert_eq!(os_release.build_id.as_deref(), Some("rolling"));
+    assert_eq!(
+        os_release.variant.as_deref(),
+        Some(r#"Workstation "Edition" \ $HOME"#)
+    );
+    assert_eq!(
+        os_release.get_display_name(),
+        "Fedora Linux 39 (Workstation Edition)"
+    );
+    assert_eq!(os_release.get_ids(), vec!["fedora", "rhel", "centos"]);
+
+    // no `PRETTY_NAME`
+    let os_release: OsRelease = "NAME=Arch\nBUILD_ID=rolling\n".parse().unwrap();
+    assert_eq!(os_release.get_display_name(), "Arch rolling");
+}
@@ -0,0 +1,84 @@
+/// get info from
+/// ```text
+/// /etc/passwd
+/// ```
+///
+/// `name:password:uid:gid:gecos:home:shell` per line
+/// NOTE: local users only, no NSS lookup (e.g. LDAP, systemd-homed users are not resolved)
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const ETC_PASSWD: &str = "/etc/passwd";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Passwd {
+    /// `BTreeMap<uid, name>`
+    pub users: BTreeMap<u32, String>,
+}
+
+impl Deref for Passwd {
+    type Target = BTreeMap<u32, String>;
+
+    fn deref(&self) -> &BTreeMap<u32, String> {
+        &self.users
+    }
+}
+
+impl DerefMut for Passwd {
+    fn deref_mut(&mut self) -> &mut BTreeMap<u32, String> {
+        &mut self.users
+    }
+}
+
+impl Passwd {
+    pub fn get() -> Result<Self, Error> {
+        let buf = get_string_from_file(ETC_PASSWD)?;
+        buf.parse()
+    }
+
+    /// user name or uid as string, if not in `/etc/passwd`
+    pub fn get_name(&self, uid: u32) -> String {
+        match self.get(&uid) {
+            Some(name) => name.clone(),
+            None => uid.to_string(),
+        }
+    }
+}
+
+impl FromStr for Passwd {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut passwd = Passwd::default();
+
+        // NOTE: first entry wins, like getpwuid(3) for duplicate uids
+        for line in s.lines().filter(|line| !line.starts_with('#')) {
+            let v: Vec<&str> = line.split(':').collect();
+            if let (Some(name), Some(uid)) = (v.first(), v.get(2).and_then(|s| s.parse().ok())) {
+                passwd.entry(uid).or_insert_with(|| name.to_string());
+            }
+        }
+
+        Ok(passwd)
+    }
+}
+
+#[test]
+fn passwd_from_str_test() {
+    let s = "root:x:0:0:root:/root:/bin/bash
+daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin
+# comment
+alice:x:1000:1000:Alice,,,:/home/alice:/bin/zsh
+toor:x:0:0::/root:/bin/sh
+broken line
+";
+    let passwd: Passwd = s.parse().unwrap();
+    assert_eq!(passwd.len(), 3);
+    assert_eq!(passwd.get_name(0), "root");
+    assert_eq!(passwd.get_name(1000), "alice");
+    assert_eq!(passwd.get_name(65534), "65534");
+}
@@ -0,0 +1,221 @@
+/// memory growth of processes over a time window
+///
+/// samples RSS (`/proc/<PID>/status`) and PSS (`/proc/<PID>/smaps_rollup`) of each process,
+/// growth is the least squares slope of PSS (RSS, if PSS is not readable)
+/// NOTE: `smaps_rollup` is expensive, processes are sampled `MEMORY_TREND_SAMPLES` times per window only
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::{Bytes, ProcList};
+
+/// samples per window (e.g. one per minute for a one hour window)
+pub const MEMORY_TREND_SAMPLES: u32 = 60;
+/// samples needed for a slope
+pub(crate) const MEMORY_TREND_MIN_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MemorySample {
+    /// seconds since start of tracking
+    pub time: f64,
+    pub rss: Bytes,
+    /// `None` if `smaps_rollup` is not readable
+    pub pss: Option<Bytes>,
+}
+
+impl MemorySample {
+    /// PSS, RSS if PSS is not readable
+    pub fn get_memory(&self) -> Bytes {
+        self.pss.unwrap_or(self.rss)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MemoryTrend {
+    pub pid: u32,
+    /// `starttime` of `/proc/<PID>/stat`, pids are reused
+    pub starttime: u64,
+    /// oldest first
+    pub samples: VecDeque<MemorySample>,
+}
+
+impl MemoryTrend {
+    /// growth (B/s) by least squares, `None` with too few samples
+    pub fn get_slope(&self) -> Option<f64> {
+        if self.samples.len() < MEMORY_TREND_MIN_SAMPLES {
+            return None;
+        }
+
+        let n = self.samples.len() as f64;
+        let mean_time = self.samples.iter().map(|s| s.time).sum::<f64>() / n;
+        let mean_memory = self
+            .samples
+            .iter()
+            .map(|s| s.get_memory().as_f64())
+            .sum::<f64>()
+            / n;
+
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for sample in self.samples.iter() {
+            let dt = sample.time - mean_time;
+            covariance += dt * (sample.get_memory().as_f64() - mean_memory);
+            variance += dt * dt;
+        }
+
+        match variance > 0.0 {
+            true => Some(covariance / variance),
+            false => None,
+        }
+    }
+
+    /// latest sample
+    pub fn get_last(&self) -> Option<&MemorySample> {
+        self.samples.back()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoryTrends {
+    /// samples older than the window are dropped
+    pub window: Duration,
+    /// time of the last sampling (s)
+    pub last: Option<f64>,
+    /// `BTreeMap<pid, MemoryTrend>`
+    pub trends: BTreeMap<u32, MemoryTrend>,
+    start: Instant,
+}
+
+impl MemoryTrends {
+    pub fn new(window: Duration) -> Self {
+        MemoryTrends {
+            window,
+            last: None,
+            trends: BTreeMap::new(),
+            start: Instant::now(),
+        }
+    }
+
+    /// sample all processes, if the sample interval is over, kernel threads skipped
+    pub fn update(&mut self, ps: &ProcList) {
+        let time = self.start.elapsed().as_secs_f64();
+        if !self.is_due(time) {
+            return;
+        }
+
+        let samples: Vec<(u32, u64, MemorySample)> = ps
+            .values()
+            .filter(|process| !process.is_kernel_thread())
+            .map(|process| {
+                let sample = MemorySample {
+                    time,
+                    rss: process.status.vm_rss,
+                    pss: process.get_smaps_rollup().map(|smaps| smaps.pss),
+                };
+                (process.pid, process.stat.starttime, sample)
+            })
+            .collect();
+        self.add_samples(time, &samples);
+    }
+
+    /// sample interval (window / `MEMORY_TREND_SAMPLES`) over
+    pub fn is_due(&self, time: f64) -> bool {
+        let interval = self.window.as_secs_f64() / MEMORY_TREND_SAMPLES as f64;
+        self.last.is_none_or(|last| time - last >= interval)
+    }
+
+    /// add samples `(pid, starttime, sample)` taken at given time,
+    /// processes without sample exited and are removed
+    pub fn add_samples(&mut self, time: f64, samples: &[(u32, u64, MemorySample)]) {
+        let mut trends = BTreeMap::new();
+
+        for (pid, starttime, sample) in samples.iter() {
+            let mut trend = match self.trends.remove(pid) {
+                Some(trend) if trend.starttime == *starttime => trend,
+                // new process or reused pid
+                _ => MemoryTrend {
+                    pid: *pid,
+                    starttime: *starttime,
+                    samples: VecDeque::new(),
+                },
+            };
+            trend.samples.push_back(*sample);
+            while trend
+                .samples
+                .front()
+                .is_some_and(|first| time - first.time > self.window.as_secs_f64())
+            {
+                trend.samples.pop_front();
+            }
+            trends.insert(*pid, trend);
+        }
+
+        self.trends = trends;
+        self.last = Some(time);
+    }
+
+    /// processes with growth above given slope (B/s), fastest growing first
+    pub fn get_growing(&self, min_slope: f64) -> Vec<(&MemoryTrend, f64)> {
+        let mut growing: Vec<(&MemoryTrend, f64)> = self
+            .trends
+            .values()
+            .filter_map(|trend| Some((trend, trend.get_slope()?)))
+            .filter(|(_, slope)| *slope > min_slope)
+            .collect();
+        growing.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        growing
+    }
+}
+
+#[test]
+fn memory_trends_test() {
+    let mut trends = MemoryTrends::new(Duration::from_secs(600));
+    assert!(trends.is_due(0.0));
+
+    // pid 10 grows by 1 KiB/s (PSS), pid 20 is flat (RSS only), pid 30 exits
+    for i in 0..5 {
+        let time = i as f64 * 60.0;
+        let mut samples = vec![
+            (
+                10,
+                100,
+                MemorySample {
+                    time,
+                    rss: Bytes(1 << 30),
+                    pss: Some(Bytes(1_000_000 + i * 60 * 1024)),
+                },
+            ),
+            (
+                20,
+                200,
+                MemorySample {
+                    time,
+                    rss: Bytes(4096),
+                    pss: None,
+                },
+            ),
+        ];
+        if i < 2 {
+            samples.push((30, 300, MemorySample::default()));
+        }
+        trends.add_samples(time, &samples);
+    }
+
+    assert!(!trends.is_due(240.0 + 5.0));
+    assert!(trends.is_due(240.0 + 10.0));
+    assert_eq!(trends.trends.len(), 2);
+
+    let growing = trends.get_growing(0.0);
+    assert_eq!(growing.len(), 1);
+    assert_eq!(growing[0].0.pid, 10);
+    assert!((growing[0].1 - 1024.0).abs() < 1e-6);
+    assert_eq!(growing[0].0.samples.len(), 5);
+
+    // reused pid starts over, old samples drop out of the window
+    let sample = MemorySample {
+        time: 800.0,
+        ..Default::default()
+    };
+    trends.add_samples(800.0, &[(10, 999, sample), (20, 200, sample)]);
+    assert_eq!(trends.trends[&10].samples.len(), 1);
+    assert_eq!(trends.trends[&20].samples.len(), 2);
+    assert_eq!(trends.trends[&20].get_slope(), None);
+}
@@ -1,17 +1,59 @@
+pub mod container_engine;
+pub mod etc_os_release;
+pub mod etc_passwd;
+pub mod memory_trend;
+pub mod proc_cmdline;
 pub mod proc_cpuinfo;
+pub mod proc_device_tree;
+pub mod proc_interrupts;
 pub mod proc_meminfo;
+pub mod proc_pid_io;
+pub mod proc_pid_smaps_rollup;
+pub mod proc_pressure;
+pub mod proc_softirqs;
 pub mod proc_stat;
+pub mod proc_swaps;
+pub mod proc_vmstat;
 pub mod process;
 pub mod sys_class_dmi;
+pub mod sys_class_powercap;
+pub mod sys_cpu_vulnerabilities;
+pub mod sys_cpuidle;
+pub mod sys_devices_system_node;
+pub mod sys_firmware_dmi;
+pub mod sys_fs_cgroup;
+pub mod sys_module_zswap;
 pub mod system;
 pub mod uname;
 pub mod uptime;
+pub mod virtualization;
 
+pub use container_engine::*;
+pub use etc_os_release::*;
+pub use etc_passwd::*;
+pub use memory_trend::*;
+pub use proc_cmdline::*;
 pub use proc_cpuinfo::*;
+pub use proc_device_tree::*;
+pub use proc_interrupts::*;
 pub use proc_meminfo::*;
+pub use proc_pid_io::*;
+pub use proc_pid_smaps_rollup::*;
+pub use proc_pressure::*;
+pub use proc_softirqs::*;
 pub use proc_stat::*;
+pub use proc_swaps::*;
+pub use proc_vmstat::*;
 pub use process::*;
 pub use sys_class_dmi::*;
+pub use sys_class_powercap::*;
+pub use sys_cpu_vulnerabilities::*;
+pub use sys_cpuidle::*;
+pub use sys_devices_system_node::*;
+pub use sys_firmware_dmi::*;
+pub use sys_fs_cgroup::*;
+pub use sys_module_zswap::*;
 pub use system::*;
 pub use uname::*;
 pub use uptime::*;
+pub use virtualization::*;
@@ -0,0 +1,73 @@
+/// get info from procfs
+/// ```text
+/// /proc/cmdline
+/// ```
+///
+/// kernel command line (e.g. `BOOT_IMAGE=/vmlinuz root=/dev/sda1 ro mitigations=auto nosmt`)
+use std::io::Error;
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const PROC_CMDLINE: &str = "/proc/cmdline";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct KernelCmdline {
+    /// options in given order, `key` or `key=value` (e.g. `("nosmt", None)`, `("mitigations", Some("auto"))`)
+    pub options: Vec<(String, Option<String>)>,
+}
+
+impl KernelCmdline {
+    pub fn get() -> Result<Self, Error> {
+        let buf = get_string_from_file(PROC_CMDLINE)?;
+        buf.parse()
+    }
+
+    /// option is given (e.g. `nosmt`)
+    pub fn contains(&self, key: &str) -> bool {
+        self.options.iter().any(|(k, _)| k == key)
+    }
+
+    /// value of the last given option, overrides previous ones (e.g. `mitigations=off` -> `off`)
+    pub fn get_value(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, v)| v.as_deref())
+    }
+}
+
+impl FromStr for KernelCmdline {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cmdline = KernelCmdline::default();
+
+        // NOTE: arguments after `--` are passed to init
+        for option in s.split_whitespace().take_while(|o| *o != "--") {
+            let (key, value) = match option.split_once('=') {
+                Some((key, value)) => (key, Some(value.trim_matches('"').to_string())),
+                None => (option, None),
+            };
+            cmdline.options.push((key.to_string(), value));
+        }
+
+        Ok(cmdline)
+    }
+}
+
+#[test]
+fn kernel_cmdline_from_str_test() {
+    let cmdline: KernelCmdline = "BOOT_IMAGE=/vmlinuz ro mitigations=auto,nosmt nosmt \
+                                  spectre_v2=on mitigations=off -- --init-arg nosmt=1"
+        .parse()
+        .unwrap();
+
+    assert!(cmdline.contains("nosmt"));
+    assert!(cmdline.contains("ro"));
+    assert!(!cmdline.contains("--init-arg"));
+    assert_eq!(cmdline.get_value("mitigations"), Some("off"));
+    assert_eq!(cmdline.get_value("spectre_v2"), Some("on"));
+    assert_eq!(cmdline.get_value("nosmt"), None);
+}
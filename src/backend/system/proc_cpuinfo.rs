@@ -20,9 +20,25 @@ pub struct CpuInfo {
     pub processor: usize,
     pub model_name: String,
     pub cpu_mhz: f64,
+    /// known CPU bugs (e.g. `spectre_v1`, `spectre_v2`, `swapgs`)
+    pub bugs: Vec<String>,
+    /// CPU features (e.g. `sse4_2`, `avx2`, `hypervisor`), `Features` on ARM
+    pub flags: Vec<String>,
+}
+
+impl CpusInfo {
+    /// `hypervisor` flag, running as a virtual machine guest (x86 only)
+    pub fn is_hypervisor(&self) -> bool {
+        self.cpus
+            .first()
+            .map(|cpu| cpu.flags.iter().any(|flag| flag == CPUINFO_FLAG_HYPERVISOR))
+            .unwrap_or_default()
+    }
 }
 
 const PROC_CPUINFO: &str = "/proc/cpuinfo";
+/// `flags` set by the CPU for virtual machine guests
+pub(crate) const CPUINFO_FLAG_HYPERVISOR: &str = "hypervisor";
 
 pub fn parse_cpuinfo_value(line: &str) -> String {
     line.splitn(2, ':')
@@ -56,6 +72,8 @@ impl FromStr for CpusInfo {
         let mut processor = 0;
         let mut model_name = String::new();
         let mut cpu_mhz = 0.0;
+        let mut bugs = Vec::new();
+        let mut flags = Vec::new();
 
         for line in s.lines() {
             // processor
@@ -73,12 +91,28 @@ impl FromStr for CpusInfo {
                 cpu_mhz = parse_cpuinfo_float_value(line)
                 // cpuinfo.cpu_mhz = parse_cpuinfo_float_value(line)
 
+                // bugs
+            } else if line.starts_with("bugs") {
+                bugs = parse_cpuinfo_value(line)
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .collect();
+
+                // flags (x86), features (ARM)
+            } else if line.starts_with("flags") || line.starts_with("Features") {
+                flags = parse_cpuinfo_value(line)
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .collect();
+
                 // current processor info block ends with empty line, including the last one
             } else if line.is_empty() {
                 cpus.cpus.push(CpuInfo {
                     processor: processor,
                     model_name: model_name.clone(),
                     cpu_mhz: cpu_mhz,
+                    bugs: bugs.clone(),
+                    flags: flags.clone(),
                 });
                 // cpus.cpus.push(cpuinfo);
             } else {
@@ -88,3 +122,22 @@ impl FromStr for CpusInfo {
         Ok(cpus)
     }
 }
+
+#[test]
+fn cpuinfo_flags_test() {
+    let s = "processor\t: 0
+model name\t: Intel(R) Core(TM) i5-8250U CPU @ 1.60GHz
+cpu MHz\t\t: 1800.000
+flags\t\t: fpu vme sse4_2 avx2 hypervisor
+vmx flags\t: vnmi
+bugs\t\t: spectre_v1 spectre_v2
+
+";
+    let cpus: CpusInfo = s.parse().unwrap();
+    assert_eq!(
+        cpus.cpus[0].flags,
+        vec!["fpu", "vme", "sse4_2", "avx2", "hypervisor"]
+    );
+    assert_eq!(cpus.cpus[0].bugs, vec!["spectre_v1", "spectre_v2"]);
+    assert!(cpus.is_hypervisor());
+}
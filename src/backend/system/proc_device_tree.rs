@@ -0,0 +1,67 @@
+/// get info from procfs
+/// ```text
+/// /proc/device-tree/model
+/// /proc/device-tree/compatible
+/// ```
+///
+/// board identification on systems without DMI (e.g. ARM boards)
+/// NOTE: values are `\0` terminated, `compatible` is a `\0` separated list, most specific first
+use std::io::Error;
+use std::path::Path;
+
+pub const PROC_DEVICE_TREE: &str = "/proc/device-tree";
+/// `/proc/device-tree/model` (e.g. `Raspberry Pi 4 Model B Rev 1.4`)
+pub(crate) const DEVICE_TREE_MODEL: &str = "model";
+/// `/proc/device-tree/compatible` (e.g. `raspberrypi,4-model-b\0brcm,bcm2711\0`)
+pub(crate) const DEVICE_TREE_COMPATIBLE: &str = "compatible";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeviceTree {
+    /// `model`
+    pub model: Option<String>,
+    /// `compatible`, `<vendor>,<device>` strings
+    pub compatible: Vec<String>,
+}
+
+/// split `\0` separated device tree property into strings
+pub fn parse_device_tree_strings(buf: &[u8]) -> Vec<String> {
+    buf.split(|b| *b == 0)
+        .map(|s| String::from_utf8_lossy(s).trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+impl DeviceTree {
+    // NOTE: empty if there is no device tree (e.g. x86 with ACPI)
+    pub fn get() -> Result<Self, Error> {
+        DeviceTree::get_from_path(Path::new(PROC_DEVICE_TREE))
+    }
+
+    pub fn get_from_path(path: &Path) -> Result<Self, Error> {
+        let read = |file| std::fs::read(path.join(file)).unwrap_or_default();
+
+        Ok(DeviceTree {
+            model: parse_device_tree_strings(&read(DEVICE_TREE_MODEL))
+                .into_iter()
+                .next(),
+            compatible: parse_device_tree_strings(&read(DEVICE_TREE_COMPATIBLE)),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none() && self.compatible.is_empty()
+    }
+}
+
+#[test]
+fn device_tree_get_test() {
+    let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/device-tree");
+
+    let dt = DeviceTree::get_from_path(&testdata.join("raspberry-pi-4")).unwrap();
+    assert_eq!(dt.model.as_deref(), Some("Raspberry Pi 4 Model B Rev 1.4"));
+    assert_eq!(dt.compatible, vec!["raspberrypi,4-model-b", "brcm,bcm2711"]);
+
+    assert!(DeviceTree::get_from_path(&testdata.join("missing"))
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,184 @@
+/// get info from procfs
+/// ```text
+/// /proc/interrupts
+/// ```
+///
+/// ```text
+///            CPU0       CPU1
+///  24:          1          0  IO-APIC    5-edge      ACPI:Ged
+///  45:     125038       9123  PCI-MSIX-0000:03:00.0    1-edge      enp3s0-TxRx-0
+/// NMI:          0          0   Non-maskable interrupts
+/// ```
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+pub const PROC_INTERRUPTS: &str = "/proc/interrupts";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Interrupt {
+    /// interrupt count per CPU (e.g. `CPU0`, `CPU1`, ...)
+    pub counts: Vec<u64>,
+    /// interrupt controller chip (e.g. `IO-APIC`, `PCI-MSIX-0000:03:00.0`), numeric IRQs only
+    pub chip: Option<String>,
+    /// hardware interrupt number and trigger type (e.g. `5-edge`), numeric IRQs only
+    pub hwirq: Option<String>,
+    /// handler names (e.g. `enp3s0-TxRx-0`) or description for named IRQs (e.g. `Local timer interrupts`)
+    pub actions: Vec<String>,
+}
+
+impl Interrupt {
+    /// interrupts per CPU between two snapshots
+    pub fn get_delta(&self, start: &Interrupt) -> Vec<u64> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, n)| n.saturating_sub(start.counts.get(i).copied().unwrap_or_default()))
+            .collect()
+    }
+}
+
+// parse line without IRQ number (e.g. `1  0  IO-APIC  5-edge  ACPI:Ged`)
+fn parse_interrupt(s: &str, cpus: usize, numeric: bool) -> Interrupt {
+    let mut interrupt = Interrupt::default();
+    let mut parts = s.split_whitespace().peekable();
+
+    // NOTE: some arch specific lines (e.g. `ERR:`, `MIS:`) have a single count only
+    while interrupt.counts.len() < cpus {
+        match parts.peek().and_then(|p| p.parse().ok()) {
+            Some(n) => {
+                interrupt.counts.push(n);
+                parts.next();
+            }
+            None => break,
+        }
+    }
+
+    let rest: Vec<&str> = parts.collect();
+
+    if numeric {
+        interrupt.chip = rest.first().map(|s| s.to_string());
+        interrupt.hwirq = rest.get(1).map(|s| s.to_string());
+        // `action1, action2`
+        interrupt.actions = rest
+            .get(2..)
+            .unwrap_or_default()
+            .join(" ")
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+    } else if !rest.is_empty() {
+        interrupt.actions.push(rest.join(" "));
+    }
+
+    interrupt
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Interrupts {
+    /// number of online CPUs (columns)
+    pub cpus: usize,
+    /// `BTreeMap<IRQ, Interrupt{}>` (e.g. `24`, `NMI`, `LOC`)
+    pub interrupts: BTreeMap<String, Interrupt>,
+}
+
+impl Deref for Interrupts {
+    type Target = BTreeMap<String, Interrupt>;
+
+    fn deref(&self) -> &BTreeMap<String, Interrupt> {
+        &self.interrupts
+    }
+}
+
+impl DerefMut for Interrupts {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, Interrupt> {
+        &mut self.interrupts
+    }
+}
+
+impl Interrupts {
+    pub fn get() -> Result<Self, Error> {
+        let buf = read_to_string(PROC_INTERRUPTS)?;
+        buf.parse()
+    }
+
+    /// IRQs of network interface queues (e.g. `enp3s0-TxRx-0`, `eth0-rx-1`, `enp3s0`)
+    pub fn get_nic_queues(&self, interface: &str) -> Vec<&String> {
+        self.iter()
+            .filter(|(_, interrupt)| {
+                interrupt.actions.iter().any(|a| {
+                    a.strip_prefix(interface)
+                        .map(|tail| tail.is_empty() || tail.starts_with(['-', '@', '.']))
+                        .unwrap_or(false)
+                })
+            })
+            .map(|(irq, _)| irq)
+            .collect()
+    }
+}
+
+impl FromStr for Interrupts {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut interrupts = Interrupts::default();
+        let mut lines = s.lines();
+
+        // header `CPU0 CPU1 ...`, offline CPUs are skipped
+        let header = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "no header"))?;
+        interrupts.cpus = header.split_whitespace().count();
+
+        for line in lines {
+            if let Some((irq, s)) = line.trim().split_once(':') {
+                let numeric = irq.chars().all(|c| c.is_ascii_digit());
+                let interrupt = parse_interrupt(s, interrupts.cpus, numeric);
+                interrupts.insert(irq.to_string(), interrupt);
+            }
+        }
+
+        Ok(interrupts)
+    }
+}
+
+#[test]
+fn proc_interrupts_from_str_test() {
+    let s = "           CPU0       CPU1\n  \
+             24:          1          0  IO-APIC    5-edge      ACPI:Ged\n  \
+             45:     125038       9123  PCI-MSIX-0000:03:00.0    1-edge      enp3s0-TxRx-0\n  \
+             46:        100      90000  PCI-MSIX-0000:03:00.0    2-edge      enp3s0-TxRx-1\n  \
+             47:          3          4  IR-PCI-MSI 1048576-edge      ahci[0000:00:17.0], snd_hda\n\
+             NMI:          0          0   Non-maskable interrupts\n\
+             ERR:          0\n";
+    let interrupts: Interrupts = s.parse().unwrap();
+
+    assert_eq!(interrupts.cpus, 2);
+
+    let irq = interrupts.get("45").unwrap();
+    assert_eq!(irq.counts, vec![125038, 9123]);
+    assert_eq!(irq.chip.as_deref(), Some("PCI-MSIX-0000:03:00.0"));
+    assert_eq!(irq.hwirq.as_deref(), Some("1-edge"));
+    assert_eq!(irq.actions, vec!["enp3s0-TxRx-0"]);
+
+    let irq = interrupts.get("47").unwrap();
+    assert_eq!(irq.actions, vec!["ahci[0000:00:17.0]", "snd_hda"]);
+
+    let irq = interrupts.get("NMI").unwrap();
+    assert_eq!(irq.chip, None);
+    assert_eq!(irq.actions, vec!["Non-maskable interrupts"]);
+
+    assert_eq!(interrupts.get("ERR").unwrap().counts, vec![0]);
+
+    assert_eq!(interrupts.get_nic_queues("enp3s0"), vec!["45", "46"]);
+    assert!(interrupts.get_nic_queues("enp3").is_empty());
+
+    let start = Interrupt {
+        counts: vec![125000, 9200],
+        ..Default::default()
+    };
+    assert_eq!(interrupts.get("45").unwrap().get_delta(&start), vec![38, 0]);
+}
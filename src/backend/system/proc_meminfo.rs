@@ -8,28 +8,89 @@
 /// * less flexibility
 /// * less values
 ///
+use std::collections::BTreeMap;
 use std::io::Error;
 use std::str::FromStr;
 
-use crate::get_string_from_file;
+use crate::{get_string_from_file, Bytes};
 
 const PROC_MEMINFO: &str = "/proc/meminfo";
 
 #[derive(Debug, PartialEq, Default)]
 pub struct MemInfo {
-    /// `MemTotal` (kB)
-    pub mem_total: u64,
-    /// `MemFree` physical RAM, left unused by the system (kB)
-    pub mem_free: u64,
-    /// `MemAvailable` memory available for starting new applications, without swapping (kB)
-    pub mem_available: u64,
-    /// `SwapTotal` (kB)
-    pub swap_total: u64,
-    /// `SwapFree` (kB)
-    pub swap_free: u64,
+    /// `MemTotal`
+    pub mem_total: Bytes,
+    /// `MemFree` physical RAM, left unused by the system
+    pub mem_free: Bytes,
+    /// `MemAvailable` memory available for starting new applications, without swapping
+    pub mem_available: Bytes,
+    /// `Buffers` temporary storage for raw disk blocks
+    pub buffers: Bytes,
+    /// `Cached` page cache, including `Shmem`
+    pub cached: Bytes,
+    /// `SwapCached` memory swapped out and back in, still in the swap file
+    pub swap_cached: Bytes,
+    /// `Active` recently used memory
+    pub active: Bytes,
+    /// `Inactive` less recently used memory, reclaim candidate
+    pub inactive: Bytes,
+    /// `Active(anon)`
+    pub active_anon: Bytes,
+    /// `Inactive(anon)`
+    pub inactive_anon: Bytes,
+    /// `Active(file)`
+    pub active_file: Bytes,
+    /// `Inactive(file)`
+    pub inactive_file: Bytes,
+    /// `SwapTotal`
+    pub swap_total: Bytes,
+    /// `SwapFree`
+    pub swap_free: Bytes,
+    /// `Zswap` memory used by the zswap pool (kernel >= 5.19)
+    pub zswap: Bytes,
+    /// `Zswapped` uncompressed size of pages stored in zswap (kernel >= 5.19)
+    pub zswapped: Bytes,
+    /// `Dirty` waiting to get written back to the disk
+    pub dirty: Bytes,
+    /// `Writeback` actively being written back to the disk
+    pub writeback: Bytes,
+    /// `AnonPages` non-file backed pages mapped into user page tables
+    pub anon_pages: Bytes,
+    /// `Mapped` files which have been mmaped
+    pub mapped: Bytes,
+    /// `Shmem` shared memory and tmpfs
+    pub shmem: Bytes,
+    /// `Slab` in-kernel data structures cache
+    pub slab: Bytes,
+    /// `SReclaimable` part of `Slab`, that might be reclaimed
+    pub s_reclaimable: Bytes,
+    /// `SUnreclaim` part of `Slab`, that cannot be reclaimed
+    pub s_unreclaim: Bytes,
+    /// `KernelStack`
+    pub kernel_stack: Bytes,
+    /// `PageTables` lowest level of page tables
+    pub page_tables: Bytes,
+    /// `CommitLimit` total amount of memory currently available to be allocated
+    pub commit_limit: Bytes,
+    /// `Committed_AS` amount of memory presently allocated
+    pub committed_as: Bytes,
+    /// `AnonHugePages` non-file backed huge pages mapped into user page tables
+    pub anon_huge_pages: Bytes,
+    /// `HugePages_Total` size of the huge pages pool (pages)
+    pub huge_pages_total: u64,
+    /// `HugePages_Free` huge pages not yet allocated (pages)
+    pub huge_pages_free: u64,
+    /// `HugePages_Rsvd` huge pages reserved, but not yet allocated (pages)
+    pub huge_pages_rsvd: u64,
+    /// `HugePages_Surp` surplus huge pages above the pool size (pages)
+    pub huge_pages_surp: u64,
+    /// `Hugepagesize`
+    pub hugepagesize: Bytes,
+    /// other keys not parsed into fields, raw values (e.g. `Percpu`, `DirectMap4k`)
+    pub other: BTreeMap<String, u64>,
 }
 
-/// parse str value into usize
+/// parse str value into u64, `kB` values are converted into `Bytes` by the caller
 /// (e.g. `MemTotal: 123456 kB` -> `123456`)
 fn parse_meminfo_usize_value(line: &str) -> u64 {
     line.splitn(2, ':')
@@ -49,6 +110,16 @@ impl MemInfo {
         let buf = get_string_from_file(PROC_MEMINFO)?;
         buf.parse()
     }
+
+    /// page cache without shared memory, plus reclaimable slab
+    pub fn cache(&self) -> Bytes {
+        self.cached + self.s_reclaimable - self.shmem
+    }
+
+    /// memory used by applications, without buffers, cache, shmem
+    pub fn apps(&self) -> Bytes {
+        self.mem_total - self.mem_free - self.buffers - self.cache() - self.shmem
+    }
 }
 
 impl FromStr for MemInfo {
@@ -58,28 +129,80 @@ impl FromStr for MemInfo {
         let mut mem_info = MemInfo::default();
 
         for line in s.lines() {
-            // MemTotal
-            if line.starts_with("MemTotal:") {
-                mem_info.mem_total = parse_meminfo_usize_value(line)
-            }
-            // MemFree
-            else if line.starts_with("MemFree:") {
-                mem_info.mem_free = parse_meminfo_usize_value(line)
-            }
-            // MemAvailable
-            else if line.starts_with("MemAvailable:") {
-                mem_info.mem_available = parse_meminfo_usize_value(line)
-            }
-            // SwapTotal
-            else if line.starts_with("SwapTotal:") {
-                mem_info.swap_total = parse_meminfo_usize_value(line)
-            }
-            // SwapFree
-            else if line.starts_with("SwapFree:") {
-                mem_info.swap_free = parse_meminfo_usize_value(line)
+            let value = parse_meminfo_usize_value(line);
+            let key = line.split_once(':').map(|(k, _)| k).unwrap_or_default();
+
+            match key {
+                "MemTotal" => mem_info.mem_total = Bytes::from_kib(value),
+                "MemFree" => mem_info.mem_free = Bytes::from_kib(value),
+                "MemAvailable" => mem_info.mem_available = Bytes::from_kib(value),
+                "Buffers" => mem_info.buffers = Bytes::from_kib(value),
+                "Cached" => mem_info.cached = Bytes::from_kib(value),
+                "SwapCached" => mem_info.swap_cached = Bytes::from_kib(value),
+                "Active" => mem_info.active = Bytes::from_kib(value),
+                "Inactive" => mem_info.inactive = Bytes::from_kib(value),
+                "Active(anon)" => mem_info.active_anon = Bytes::from_kib(value),
+                "Inactive(anon)" => mem_info.inactive_anon = Bytes::from_kib(value),
+                "Active(file)" => mem_info.active_file = Bytes::from_kib(value),
+                "Inactive(file)" => mem_info.inactive_file = Bytes::from_kib(value),
+                "SwapTotal" => mem_info.swap_total = Bytes::from_kib(value),
+                "SwapFree" => mem_info.swap_free = Bytes::from_kib(value),
+                "Zswap" => mem_info.zswap = Bytes::from_kib(value),
+                "Zswapped" => mem_info.zswapped = Bytes::from_kib(value),
+                "Dirty" => mem_info.dirty = Bytes::from_kib(value),
+                "Writeback" => mem_info.writeback = Bytes::from_kib(value),
+                "AnonPages" => mem_info.anon_pages = Bytes::from_kib(value),
+                "Mapped" => mem_info.mapped = Bytes::from_kib(value),
+                "Shmem" => mem_info.shmem = Bytes::from_kib(value),
+                "Slab" => mem_info.slab = Bytes::from_kib(value),
+                "SReclaimable" => mem_info.s_reclaimable = Bytes::from_kib(value),
+                "SUnreclaim" => mem_info.s_unreclaim = Bytes::from_kib(value),
+                "KernelStack" => mem_info.kernel_stack = Bytes::from_kib(value),
+                "PageTables" => mem_info.page_tables = Bytes::from_kib(value),
+                "CommitLimit" => mem_info.commit_limit = Bytes::from_kib(value),
+                "Committed_AS" => mem_info.committed_as = Bytes::from_kib(value),
+                "AnonHugePages" => mem_info.anon_huge_pages = Bytes::from_kib(value),
+                "HugePages_Total" => mem_info.huge_pages_total = value,
+                "HugePages_Free" => mem_info.huge_pages_free = value,
+                "HugePages_Rsvd" => mem_info.huge_pages_rsvd = value,
+                "HugePages_Surp" => mem_info.huge_pages_surp = value,
+                "Hugepagesize" => mem_info.hugepagesize = Bytes::from_kib(value),
+                _ => {
+                    mem_info.other.insert(key.to_string(), value);
+                }
             }
         }
 
         Ok(mem_info)
     }
 }
+
+#[test]
+fn meminfo_from_str_test() {
+    let s = "MemTotal:        6158152 kB\n\
+             MemFree:         4638028 kB\n\
+             MemAvailable:    5692500 kB\n\
+             Buffers:           59340 kB\n\
+             Cached:          1199404 kB\n\
+             Active(anon):         20 kB\n\
+             Inactive(file):   818188 kB\n\
+             Shmem:              9484 kB\n\
+             SReclaimable:      25740 kB\n\
+             Committed_AS:     336004 kB\n\
+             HugePages_Total:       4\n\
+             DirectMap2M:     2072576 kB";
+    let mem_info: MemInfo = s.parse().unwrap();
+
+    assert_eq!(mem_info.mem_total, Bytes(6158152 * 1024));
+    assert_eq!(mem_info.active_anon, Bytes(20 * 1024));
+    assert_eq!(mem_info.inactive_file, Bytes(818188 * 1024));
+    assert_eq!(mem_info.committed_as, Bytes(336004 * 1024));
+    assert_eq!(mem_info.huge_pages_total, 4);
+    assert_eq!(mem_info.other.get("DirectMap2M"), Some(&2072576));
+
+    assert_eq!(mem_info.cache(), Bytes((1199404 + 25740 - 9484) * 1024));
+    assert_eq!(
+        mem_info.apps(),
+        Bytes((6158152 - 4638028 - 59340 - 9484) * 1024) - mem_info.cache()
+    );
+}
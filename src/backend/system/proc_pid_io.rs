@@ -0,0 +1,128 @@
+/// get info from procfs
+/// ```text
+/// /proc/<PID>/io
+/// ```
+///
+/// I/O counters of a process (threads included) since start
+/// NOTE: readable for own processes only, other users' processes require root (ptrace access)
+/// INFO: [kernel.org](https://docs.kernel.org/filesystems/proc.html#proc-pid-io-display-the-io-accounting-fields)
+use std::io::Error;
+use std::str::FromStr;
+
+use crate::Bytes;
+
+/// `/proc/<PID>/io`
+pub(crate) const PROCESS_IO: &str = "io";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessIo {
+    /// `rchar` read by syscalls, page cache hits included
+    pub rchar: Bytes,
+    /// `wchar` written by syscalls
+    pub wchar: Bytes,
+    /// `syscr` read syscalls
+    pub syscr: u64,
+    /// `syscw` write syscalls
+    pub syscw: u64,
+    /// `read_bytes` fetched from the storage layer
+    pub read_bytes: Bytes,
+    /// `write_bytes` sent to the storage layer
+    pub write_bytes: Bytes,
+    /// `cancelled_write_bytes` dirty page cache truncated before writeback (e.g. deleted files)
+    pub cancelled_write_bytes: Bytes,
+}
+
+/// per second rates between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ProcessIoRate {
+    /// storage read (B/s)
+    pub read: f64,
+    /// storage write (B/s)
+    pub write: f64,
+    /// cancelled write (B/s)
+    pub cancelled_write: f64,
+    /// read syscalls (1/s)
+    pub syscr: f64,
+    /// write syscalls (1/s)
+    pub syscw: f64,
+}
+
+impl ProcessIoRate {
+    /// storage throughput, read and write (B/s)
+    pub fn total(&self) -> f64 {
+        self.read + self.write
+    }
+}
+
+impl ProcessIo {
+    pub fn get_rate(&self, start: &ProcessIo, dt: u64) -> ProcessIoRate {
+        let rate = |end: u64, start: u64| end.saturating_sub(start) as f64 / dt as f64;
+
+        ProcessIoRate {
+            read: rate(self.read_bytes.as_u64(), start.read_bytes.as_u64()),
+            write: rate(self.write_bytes.as_u64(), start.write_bytes.as_u64()),
+            cancelled_write: rate(
+                self.cancelled_write_bytes.as_u64(),
+                start.cancelled_write_bytes.as_u64(),
+            ),
+            syscr: rate(self.syscr, start.syscr),
+            syscw: rate(self.syscw, start.syscw),
+        }
+    }
+}
+
+impl FromStr for ProcessIo {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut io = ProcessIo::default();
+
+        for line in s.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().parse::<u64>().unwrap_or_default();
+
+            match key {
+                "rchar" => io.rchar = Bytes(value),
+                "wchar" => io.wchar = Bytes(value),
+                "syscr" => io.syscr = value,
+                "syscw" => io.syscw = value,
+                "read_bytes" => io.read_bytes = Bytes(value),
+                "write_bytes" => io.write_bytes = Bytes(value),
+                "cancelled_write_bytes" => io.cancelled_write_bytes = Bytes(value),
+                _ => {}
+            }
+        }
+
+        Ok(io)
+    }
+}
+
+#[test]
+fn process_io_from_str_test() {
+    let s = "rchar: 323934931
+wchar: 323929600
+syscr: 632687
+syscw: 632675
+read_bytes: 4096
+write_bytes: 323932160
+cancelled_write_bytes: 0
+";
+    let start: ProcessIo = s.parse().unwrap();
+    assert_eq!(start.syscr, 632687);
+    assert_eq!(start.read_bytes, Bytes(4096));
+    assert_eq!(start.write_bytes, Bytes(323932160));
+
+    let end = ProcessIo {
+        read_bytes: Bytes(4096 + 2 * 1024 * 1024),
+        write_bytes: Bytes(323932160 + 1024),
+        syscr: 632687 + 20,
+        ..start
+    };
+    let rate = end.get_rate(&start, 2);
+    assert_eq!(rate.read, 1024.0 * 1024.0);
+    assert_eq!(rate.write, 512.0);
+    assert_eq!(rate.syscr, 10.0);
+    assert_eq!(rate.total(), 1024.0 * 1024.0 + 512.0);
+}
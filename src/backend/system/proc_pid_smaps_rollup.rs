@@ -0,0 +1,92 @@
+/// get info from procfs
+/// ```text
+/// /proc/<PID>/smaps_rollup
+/// ```
+///
+/// memory of all mappings of a process summed up (kernel >= 4.14)
+/// NOTE: readable for own processes only, other users' processes require root (ptrace access)
+/// NOTE: walks the page tables of the process, expensive for large processes
+/// INFO: [kernel.org](https://docs.kernel.org/filesystems/proc.html#proc-pid-smaps-rollup)
+use std::io::Error;
+use std::str::FromStr;
+
+use crate::Bytes;
+
+/// `/proc/<PID>/smaps_rollup`
+pub(crate) const PROCESS_SMAPS_ROLLUP: &str = "smaps_rollup";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SmapsRollup {
+    /// `Rss` resident memory
+    pub rss: Bytes,
+    /// `Pss` proportional set size, shared pages divided by number of sharing processes
+    pub pss: Bytes,
+    /// `Pss_Anon`
+    pub pss_anon: Bytes,
+    /// `Pss_File`
+    pub pss_file: Bytes,
+    /// `Pss_Shmem`
+    pub pss_shmem: Bytes,
+    /// `Swap`
+    pub swap: Bytes,
+    /// `SwapPss` proportional swap
+    pub swap_pss: Bytes,
+}
+
+impl FromStr for SmapsRollup {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut smaps = SmapsRollup::default();
+
+        // first line is the address range (e.g. `55c5ca08c000-7ffe63b11000 ---p ... [rollup]`)
+        for line in s.lines().skip(1) {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            // e.g. `Rss:                1432 kB`
+            let value = Bytes::from_kib(
+                value
+                    .split_whitespace()
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or_default(),
+            );
+
+            match key {
+                "Rss" => smaps.rss = value,
+                "Pss" => smaps.pss = value,
+                "Pss_Anon" => smaps.pss_anon = value,
+                "Pss_File" => smaps.pss_file = value,
+                "Pss_Shmem" => smaps.pss_shmem = value,
+                "Swap" => smaps.swap = value,
+                "SwapPss" => smaps.swap_pss = value,
+                _ => {}
+            }
+        }
+
+        Ok(smaps)
+    }
+}
+
+#[test]
+fn smaps_rollup_from_str_test() {
+    let s = "55c5ca08c000-7ffe63b11000 ---p 00000000 00:00 0                          [rollup]
+Rss:                1432 kB
+Pss:                 477 kB
+Pss_Dirty:           100 kB
+Pss_Anon:            100 kB
+Pss_File:            377 kB
+Pss_Shmem:             0 kB
+Shared_Clean:       1284 kB
+Swap:                 12 kB
+SwapPss:               4 kB
+";
+    let smaps: SmapsRollup = s.parse().unwrap();
+    assert_eq!(smaps.rss, Bytes::from_kib(1432));
+    assert_eq!(smaps.pss, Bytes::from_kib(477));
+    assert_eq!(smaps.pss_anon, Bytes::from_kib(100));
+    assert_eq!(smaps.pss_file, Bytes::from_kib(377));
+    assert_eq!(smaps.swap, Bytes::from_kib(12));
+    assert_eq!(smaps.swap_pss, Bytes::from_kib(4));
+}
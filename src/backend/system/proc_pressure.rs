@@ -0,0 +1,173 @@
+/// get info from procfs
+/// ```text
+/// /proc/pressure/cpu
+/// /proc/pressure/memory
+/// /proc/pressure/io
+/// /proc/pressure/irq
+/// /sys/fs/cgroup/<CGROUP>/*.pressure
+/// ```
+///
+/// PSI (Pressure Stall Information), kernel >= 4.20 with `CONFIG_PSI=y`
+/// INFO: [kernel.org](https://docs.kernel.org/accounting/psi.html)
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const PROC_PRESSURE: &str = "/proc/pressure";
+/// `/proc/pressure/cpu`
+pub(crate) const PRESSURE_CPU: &str = "cpu";
+/// `/proc/pressure/memory`
+pub(crate) const PRESSURE_MEMORY: &str = "memory";
+/// `/proc/pressure/io`
+pub(crate) const PRESSURE_IO: &str = "io";
+/// `/proc/pressure/irq` (kernel >= 6.1, `full` line only)
+pub(crate) const PRESSURE_IRQ: &str = "irq";
+/// `/sys/fs/cgroup/<CGROUP>/<RESOURCE>.pressure`
+pub(crate) const CGROUP_PRESSURE_SUFFIX: &str = ".pressure";
+
+// `some avg10=4.45 avg60=3.33 avg300=1.01 total=3788606`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PressureLine {
+    /// share of time stalled over the last 10 s (%)
+    pub avg10: f64,
+    /// share of time stalled over the last 60 s (%)
+    pub avg60: f64,
+    /// share of time stalled over the last 300 s (%)
+    pub avg300: f64,
+    /// absolute stall time (us)
+    pub total: u64,
+}
+
+impl PressureLine {
+    /// stall time between two snapshots as percent of `dt` seconds
+    pub fn get_stall_percent(&self, start: &PressureLine, dt: u64) -> f64 {
+        let delta = self.total.saturating_sub(start.total) as f64;
+        delta / (dt as f64 * 1_000_000.0) * 100.0
+    }
+}
+
+impl FromStr for PressureLine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut line = PressureLine::default();
+
+        for (key, value) in s.split_whitespace().filter_map(|kv| kv.split_once('=')) {
+            match key {
+                "avg10" => line.avg10 = value.parse().unwrap_or_default(),
+                "avg60" => line.avg60 = value.parse().unwrap_or_default(),
+                "avg300" => line.avg300 = value.parse().unwrap_or_default(),
+                "total" => line.total = value.parse().unwrap_or_default(),
+                _ => {}
+            }
+        }
+
+        Ok(line)
+    }
+}
+
+// `some` - at least one task stalled, `full` - all non-idle tasks stalled
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pressure {
+    pub some: Option<PressureLine>,
+    pub full: Option<PressureLine>,
+}
+
+impl Pressure {
+    // get pressure by given file path (e.g. `/proc/pressure/cpu`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let buf = get_string_from_file(path)?;
+        buf.parse()
+    }
+}
+
+impl FromStr for Pressure {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut pressure = Pressure::default();
+
+        for line in s.lines() {
+            if let Some((kind, values)) = line.split_once(' ') {
+                match kind {
+                    "some" => pressure.some = Some(values.parse()?),
+                    "full" => pressure.full = Some(values.parse()?),
+                    _ => {}
+                }
+            }
+        }
+
+        if pressure.some.is_none() && pressure.full.is_none() {
+            return Err(Error::new(ErrorKind::InvalidData, "no pressure lines"));
+        }
+
+        Ok(pressure)
+    }
+}
+
+// NOTE: `None` if the kernel has no PSI support or the resource is not tracked
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pressures {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+    pub irq: Option<Pressure>,
+}
+
+impl Pressures {
+    // get system wide pressure from `/proc/pressure/*`
+    pub fn get() -> Result<Self, Error> {
+        let path = Path::new(PROC_PRESSURE);
+
+        Ok(Pressures {
+            cpu: Pressure::get(path.join(PRESSURE_CPU)).ok(),
+            memory: Pressure::get(path.join(PRESSURE_MEMORY)).ok(),
+            io: Pressure::get(path.join(PRESSURE_IO)).ok(),
+            irq: Pressure::get(path.join(PRESSURE_IRQ)).ok(),
+        })
+    }
+
+    // get cgroup v2 pressure from `<CGROUP>/{cpu,memory,io,irq}.pressure`
+    pub fn get_cgroup<P: AsRef<Path>>(cgroup: P) -> Result<Self, Error> {
+        let path = cgroup.as_ref();
+        let file = |name: &str| path.join(format!("{}{}", name, CGROUP_PRESSURE_SUFFIX));
+
+        Ok(Pressures {
+            cpu: Pressure::get(file(PRESSURE_CPU)).ok(),
+            memory: Pressure::get(file(PRESSURE_MEMORY)).ok(),
+            io: Pressure::get(file(PRESSURE_IO)).ok(),
+            irq: Pressure::get(file(PRESSURE_IRQ)).ok(),
+        })
+    }
+
+    /// PSI available at all
+    pub fn is_supported(&self) -> bool {
+        self.cpu.is_some() || self.memory.is_some() || self.io.is_some() || self.irq.is_some()
+    }
+}
+
+#[test]
+fn pressure_from_str_test() {
+    let s = "some avg10=4.45 avg60=3.33 avg300=1.01 total=3788606\n\
+             full avg10=0.00 avg60=0.00 avg300=0.00 total=0";
+    let pressure: Pressure = s.parse().unwrap();
+
+    let some = pressure.some.unwrap();
+    assert_eq!(some.avg10, 4.45);
+    assert_eq!(some.avg300, 1.01);
+    assert_eq!(some.total, 3788606);
+    assert_eq!(pressure.full.unwrap().total, 0);
+
+    // irq: `full` only
+    let irq: Pressure = "full avg10=0.10 avg60=0.00 avg300=0.00 total=1000000"
+        .parse()
+        .unwrap();
+    assert!(irq.some.is_none());
+
+    let start = PressureLine::default();
+    assert_eq!(irq.full.unwrap().get_stall_percent(&start, 2), 50.0);
+
+    assert!("".parse::<Pressure>().is_err());
+}
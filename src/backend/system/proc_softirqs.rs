@@ -0,0 +1,84 @@
+/// get info from procfs
+/// ```text
+/// /proc/softirqs
+/// ```
+///
+/// ```text
+///                     CPU0       CPU1
+///           HI:          0          1
+///       NET_RX:        325       1024
+/// ```
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+pub const PROC_SOFTIRQS: &str = "/proc/softirqs";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SoftIrqs {
+    /// number of online CPUs (columns)
+    pub cpus: usize,
+    /// `BTreeMap<name, counts per CPU>` (e.g. `NET_RX`, `TIMER`)
+    pub softirqs: BTreeMap<String, Vec<u64>>,
+}
+
+impl Deref for SoftIrqs {
+    type Target = BTreeMap<String, Vec<u64>>;
+
+    fn deref(&self) -> &BTreeMap<String, Vec<u64>> {
+        &self.softirqs
+    }
+}
+
+impl DerefMut for SoftIrqs {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, Vec<u64>> {
+        &mut self.softirqs
+    }
+}
+
+impl SoftIrqs {
+    pub fn get() -> Result<Self, Error> {
+        let buf = read_to_string(PROC_SOFTIRQS)?;
+        buf.parse()
+    }
+}
+
+impl FromStr for SoftIrqs {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut softirqs = SoftIrqs::default();
+        let mut lines = s.lines();
+
+        let header = lines
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "no header"))?;
+        softirqs.cpus = header.split_whitespace().count();
+
+        for line in lines {
+            if let Some((name, s)) = line.trim().split_once(':') {
+                let counts = s
+                    .split_whitespace()
+                    .map(|n| n.parse().unwrap_or_default())
+                    .collect();
+                softirqs.insert(name.to_string(), counts);
+            }
+        }
+
+        Ok(softirqs)
+    }
+}
+
+#[test]
+fn proc_softirqs_from_str_test() {
+    let s = "                    CPU0       CPU1\n\
+             \x20         HI:          0          1\n\
+             \x20     NET_RX:        325       1024\n";
+    let softirqs: SoftIrqs = s.parse().unwrap();
+
+    assert_eq!(softirqs.cpus, 2);
+    assert_eq!(softirqs.get("HI").unwrap(), &vec![0, 1]);
+    assert_eq!(softirqs.get("NET_RX").unwrap(), &vec![325, 1024]);
+}
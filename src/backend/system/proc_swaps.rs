@@ -0,0 +1,195 @@
+/// get info from procfs
+/// ```text
+/// /proc/swaps
+/// ```
+///
+/// active swap areas, sizes in `kB` (KiB)
+/// ```text
+/// Filename                                Type            Size            Used            Priority
+/// /dev/sda5                               partition       2097148         10240           -2
+/// /swapfile                               file            1048572         0               -3
+/// ```
+///
+/// NOTE: higher priority areas are used first, areas with the same priority round-robin
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::{get_string_from_file, Bytes};
+
+pub const PROC_SWAPS: &str = "/proc/swaps";
+/// `/proc/swaps` header line
+pub(crate) const SWAPS_HEADER: &str = "Filename";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SwapType {
+    /// `partition` block device (e.g. `/dev/sda5`, `/dev/zram0`, `/dev/dm-1`)
+    #[default]
+    Partition,
+    /// `file` swap file on a mounted filesystem (e.g. `/swapfile`)
+    File,
+    /// other, unknown type
+    Other(String),
+}
+
+impl FromStr for SwapType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "partition" => SwapType::Partition,
+            "file" => SwapType::File,
+            _ => SwapType::Other(s.to_string()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SwapArea {
+    /// `Filename` path of device or file, octal escapes decoded (e.g. `\040` -> ` `)
+    pub filename: String,
+    /// `Type`
+    pub swap_type: SwapType,
+    /// `Size`
+    pub size: Bytes,
+    /// `Used`
+    pub used: Bytes,
+    /// `Priority`
+    pub priority: i64,
+}
+
+/// decode octal escapes used by the kernel in procfs paths (e.g. `my\040swap` -> `my swap`)
+pub fn unescape_octal(s: &str) -> String {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+
+    while let Some((&b, tail)) = rest.split_first() {
+        match (b, tail.get(..3)) {
+            (b'\\', Some(digits)) if digits.iter().all(|d| (b'0'..=b'7').contains(d)) => {
+                let n = digits.iter().fold(0u32, |n, d| n * 8 + (d - b'0') as u32);
+                bytes.push(n as u8);
+                rest = &tail[3..];
+            }
+            _ => {
+                bytes.push(b);
+                rest = tail;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+impl FromStr for SwapArea {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v: Vec<&str> = s.split_whitespace().collect();
+        let field = |i: usize| {
+            v.get(i)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "swaps: missing field"))
+        };
+        let number = |i: usize| -> Result<u64, Error> {
+            field(i)?
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "swaps: invalid number"))
+        };
+
+        Ok(SwapArea {
+            filename: unescape_octal(field(0)?),
+            swap_type: field(1)?.parse()?,
+            size: Bytes::from_kib(number(2)?),
+            used: Bytes::from_kib(number(3)?),
+            priority: field(4)?
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "swaps: invalid priority"))?,
+        })
+    }
+}
+
+/// swap areas in `/proc/swaps` order (activation order)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Swaps {
+    pub areas: Vec<SwapArea>,
+}
+
+impl Deref for Swaps {
+    type Target = Vec<SwapArea>;
+
+    fn deref(&self) -> &Vec<SwapArea> {
+        &self.areas
+    }
+}
+
+impl DerefMut for Swaps {
+    fn deref_mut(&mut self) -> &mut Vec<SwapArea> {
+        &mut self.areas
+    }
+}
+
+impl Swaps {
+    // NOTE: empty if no swap is active
+    pub fn get() -> Result<Self, Error> {
+        let buf = get_string_from_file(PROC_SWAPS)?;
+        buf.parse()
+    }
+
+    /// areas in the order the kernel fills them (highest priority first)
+    pub fn by_priority(&self) -> Vec<&SwapArea> {
+        let mut areas: Vec<&SwapArea> = self.areas.iter().collect();
+        areas.sort_by_key(|area| std::cmp::Reverse(area.priority));
+        areas
+    }
+}
+
+impl FromStr for Swaps {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut swaps = Swaps::default();
+
+        for line in s.lines().filter(|line| !line.starts_with(SWAPS_HEADER)) {
+            if !line.trim().is_empty() {
+                swaps.push(line.parse()?);
+            }
+        }
+
+        Ok(swaps)
+    }
+}
+
+#[test]
+fn swaps_from_str_test() {
+    let s = "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/sda5                               partition\t2097148\t\t10240\t\t-2
+/mnt/my\\040swap                         file\t\t1048572\t\t0\t\t10
+/dev/zram0                              partition\t4194300\t\t512\t\t100
+";
+    let swaps: Swaps = s.parse().unwrap();
+    assert_eq!(swaps.len(), 3);
+    assert_eq!(
+        swaps[0],
+        SwapArea {
+            filename: "/dev/sda5".to_string(),
+            swap_type: SwapType::Partition,
+            size: Bytes::from_kib(2097148),
+            used: Bytes::from_kib(10240),
+            priority: -2,
+        }
+    );
+    assert_eq!(swaps[1].filename, "/mnt/my swap");
+    assert_eq!(swaps[1].swap_type, SwapType::File);
+
+    let order: Vec<&str> = swaps
+        .by_priority()
+        .iter()
+        .map(|area| area.filename.as_str())
+        .collect();
+    assert_eq!(order, vec!["/dev/zram0", "/mnt/my swap", "/dev/sda5"]);
+
+    // no swap active, header only
+    let swaps: Swaps = "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n"
+        .parse()
+        .unwrap();
+    assert!(swaps.is_empty());
+}
@@ -0,0 +1,122 @@
+/// get info from procfs
+/// ```text
+/// /proc/vmstat
+/// ```
+///
+/// virtual memory event counters since boot (e.g. `pswpin 0`, `pgfault 3291168`)
+/// NOTE: counter names differ between kernel versions (e.g. `workingset_refault` before 5.9)
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const PROC_VMSTAT: &str = "/proc/vmstat";
+
+/// `pswpin` pages swapped in
+pub const VMSTAT_PSWPIN: &str = "pswpin";
+/// `pswpout` pages swapped out
+pub const VMSTAT_PSWPOUT: &str = "pswpout";
+/// `pgfault` page faults (minor and major)
+pub const VMSTAT_PGFAULT: &str = "pgfault";
+/// `pgmajfault` major page faults, disk I/O required
+pub const VMSTAT_PGMAJFAULT: &str = "pgmajfault";
+/// `pgscan_kswapd` pages scanned by the background reclaim
+pub const VMSTAT_PGSCAN_KSWAPD: &str = "pgscan_kswapd";
+/// `pgscan_direct` pages scanned by the direct reclaim, allocating task stalled
+pub const VMSTAT_PGSCAN_DIRECT: &str = "pgscan_direct";
+/// `pgsteal_kswapd` pages reclaimed by the background reclaim
+pub const VMSTAT_PGSTEAL_KSWAPD: &str = "pgsteal_kswapd";
+/// `pgsteal_direct` pages reclaimed by the direct reclaim
+pub const VMSTAT_PGSTEAL_DIRECT: &str = "pgsteal_direct";
+/// `thp_fault_alloc` transparent huge pages allocated on page fault
+pub const VMSTAT_THP_FAULT_ALLOC: &str = "thp_fault_alloc";
+/// `thp_collapse_alloc` transparent huge pages collapsed by `khugepaged`
+pub const VMSTAT_THP_COLLAPSE_ALLOC: &str = "thp_collapse_alloc";
+/// `oom_kill` processes killed by the OOM killer
+pub const VMSTAT_OOM_KILL: &str = "oom_kill";
+/// `workingset_refault_anon` (kernel >= 5.9)
+pub const VMSTAT_WORKINGSET_REFAULT_ANON: &str = "workingset_refault_anon";
+/// `workingset_refault_file` (kernel >= 5.9), `workingset_refault` before
+pub const VMSTAT_WORKINGSET_REFAULT_FILE: &str = "workingset_refault_file";
+pub const VMSTAT_WORKINGSET_REFAULT: &str = "workingset_refault";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VmStat {
+    /// `BTreeMap<counter, value>`
+    pub counters: BTreeMap<String, u64>,
+}
+
+impl Deref for VmStat {
+    type Target = BTreeMap<String, u64>;
+
+    fn deref(&self) -> &BTreeMap<String, u64> {
+        &self.counters
+    }
+}
+
+impl DerefMut for VmStat {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, u64> {
+        &mut self.counters
+    }
+}
+
+impl VmStat {
+    pub fn get() -> Result<Self, Error> {
+        let buf = get_string_from_file(PROC_VMSTAT)?;
+        buf.parse()
+    }
+
+    /// counter value, `0` if not available in this kernel
+    pub fn get_counter(&self, key: &str) -> u64 {
+        self.get(key).copied().unwrap_or_default()
+    }
+
+    /// counter increment per second between two snapshots
+    pub fn get_rate(&self, start: &VmStat, key: &str, dt: u64) -> f64 {
+        let delta = self.get_counter(key).saturating_sub(start.get_counter(key));
+        delta as f64 / dt as f64
+    }
+
+    /// file refaults, named `workingset_refault` on kernels < 5.9
+    pub fn get_refault_file_key(&self) -> &'static str {
+        if self.contains_key(VMSTAT_WORKINGSET_REFAULT_FILE) {
+            VMSTAT_WORKINGSET_REFAULT_FILE
+        } else {
+            VMSTAT_WORKINGSET_REFAULT
+        }
+    }
+}
+
+impl FromStr for VmStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vmstat = VmStat::default();
+
+        for line in s.lines() {
+            if let Some((key, value)) = line.split_once(' ') {
+                vmstat.insert(key.to_string(), value.trim().parse().unwrap_or_default());
+            }
+        }
+
+        Ok(vmstat)
+    }
+}
+
+#[test]
+fn vmstat_from_str_test() {
+    let start: VmStat = "pswpin 10\npgfault 3291168\nworkingset_refault 5"
+        .parse()
+        .unwrap();
+    let end: VmStat = "pswpin 30\npgfault 3291268\nworkingset_refault 5"
+        .parse()
+        .unwrap();
+
+    assert_eq!(end.get_counter(VMSTAT_PGFAULT), 3291268);
+    assert_eq!(end.get_counter(VMSTAT_OOM_KILL), 0);
+    assert_eq!(end.get_rate(&start, VMSTAT_PSWPIN, 2), 10.0);
+    assert_eq!(end.get_rate(&start, VMSTAT_PGFAULT, 2), 50.0);
+    assert_eq!(end.get_refault_file_key(), VMSTAT_WORKINGSET_REFAULT);
+}
@@ -1,56 +1,454 @@
-use std::{collections::BTreeMap, io::Error};
+/// get info from procfs
+/// ```text
+/// /proc/<PID>/stat
+/// /proc/<PID>/status
+/// /proc/<PID>/cmdline
+/// /proc/<PID>/io
+/// /proc/<PID>/smaps_rollup
+/// ```
+///
+/// NOTE: processes can exit while being read, such processes are skipped
+/// INFO: [man7.org](https://man7.org/linux/man-pages/man5/proc_pid_stat.5.html)
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::str::FromStr;
 
 use crate::backend::get_string_from_file;
+use crate::{Bytes, ProcessIo, ProcessIoRate, SmapsRollup, PROCESS_IO, PROCESS_SMAPS_ROLLUP};
 
-#[derive(Debug, Default)]
-pub struct Proc {
-    pub name: String,
-    pub id: u64,
+pub const PROC: &str = "/proc/";
+/// `/proc/<PID>/stat`
+pub(crate) const PROCESS_STAT: &str = "stat";
+/// `/proc/<PID>/status`
+pub(crate) const PROCESS_STATUS: &str = "status";
+/// `/proc/<PID>/cmdline`
+pub(crate) const PROCESS_CMDLINE: &str = "cmdline";
+
+/// `flags` bit of kernel threads (`PF_KTHREAD` in `include/linux/sched.h`)
+pub(crate) const PF_KTHREAD: u64 = 0x0020_0000;
+
+/// `/proc/<PID>/stat`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessStat {
+    /// `(2) comm` executable name, max. 15 chars, may contain spaces and parentheses
+    pub comm: String,
+    /// `(3) state` (e.g. `R` running, `S` sleeping, `D` disk sleep, `Z` zombie, `I` idle)
+    pub state: char,
+    /// `(4) ppid` parent process id
+    pub ppid: u32,
+    /// `(9) flags` kernel flags (e.g. `PF_KTHREAD`)
+    pub flags: u64,
+    /// `(12) majflt` major page faults
+    pub majflt: u64,
+    /// `(14) utime` user mode time (clock ticks)
+    pub utime: u64,
+    /// `(15) stime` kernel mode time (clock ticks)
+    pub stime: u64,
+    /// `(18) priority`
+    pub priority: i64,
+    /// `(19) nice` (-20 .. 19)
+    pub nice: i64,
+    /// `(20) num_threads`
+    pub num_threads: u64,
+    /// `(22) starttime` since boot (clock ticks)
+    pub starttime: u64,
+    /// `(23) vsize` virtual memory size
+    pub vsize: Bytes,
+    /// `(24) rss` resident set size
+    pub rss: Bytes,
+    /// `(39) processor` CPU last executed on
+    pub processor: usize,
 }
 
-pub const PROC: &str = "/proc/";
+impl FromStr for ProcessStat {
+    type Err = Error;
+
+    // NOTE: `comm` is between the first `(` and the last `)`, e.g. `1234 (my (app)) S 1 ...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::new(ErrorKind::InvalidData, "stat: invalid format");
+
+        let start = s.find('(').ok_or_else(invalid)?;
+        let end = s.rfind(')').ok_or_else(invalid)?;
+        let comm = s.get(start + 1..end).ok_or_else(invalid)?;
+
+        // fields after `comm`, starting with `(3) state`
+        let v: Vec<&str> = s[end + 1..].split_whitespace().collect();
+        let field = |n: usize| v.get(n - 3).copied().ok_or_else(invalid);
+        let number = |n: usize| field(n).and_then(|f| f.parse::<u64>().map_err(|_| invalid()));
+        let signed = |n: usize| field(n).and_then(|f| f.parse::<i64>().map_err(|_| invalid()));
+
+        Ok(ProcessStat {
+            comm: comm.to_string(),
+            state: field(3)?.chars().next().unwrap_or('?'),
+            ppid: number(4)? as u32,
+            flags: number(9)?,
+            majflt: number(12)?,
+            utime: number(14)?,
+            stime: number(15)?,
+            priority: signed(18)?,
+            nice: signed(19)?,
+            num_threads: number(20)?,
+            starttime: number(22)?,
+            vsize: Bytes(number(23)?),
+            rss: Bytes::from_pages(number(24)?),
+            // NOTE: not present on very old kernels
+            processor: number(39).unwrap_or_default() as usize,
+        })
+    }
+}
+
+/// `/proc/<PID>/status`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ProcessStatus {
+    /// `Uid` real user id
+    pub uid: u32,
+    /// `Gid` real group id
+    pub gid: u32,
+    /// `Threads`
+    pub threads: u64,
+    /// `VmRSS` resident memory, `0` for kernel threads
+    pub vm_rss: Bytes,
+    /// `VmSwap` swapped out anonymous memory
+    pub vm_swap: Bytes,
+    /// `voluntary_ctxt_switches` (e.g. waiting for I/O)
+    pub voluntary_ctxt_switches: u64,
+    /// `nonvoluntary_ctxt_switches` preempted by scheduler
+    pub nonvoluntary_ctxt_switches: u64,
+}
+
+impl FromStr for ProcessStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut status = ProcessStatus::default();
+
+        for line in s.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            // first value (e.g. `Uid: 1000 1000 1000 1000` real, effective, saved, fs)
+            let first = value.split_whitespace().next().unwrap_or_default();
+            let number = || first.parse::<u64>().unwrap_or_default();
+
+            match key {
+                "Uid" => status.uid = number() as u32,
+                "Gid" => status.gid = number() as u32,
+                "Threads" => status.threads = number(),
+                "VmRSS" => status.vm_rss = Bytes::from_kib(number()),
+                "VmSwap" => status.vm_swap = Bytes::from_kib(number()),
+                "voluntary_ctxt_switches" => status.voluntary_ctxt_switches = number(),
+                "nonvoluntary_ctxt_switches" => status.nonvoluntary_ctxt_switches = number(),
+                _ => {}
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+/// `\0` separated arguments, empty for kernel threads and zombies
+pub fn parse_cmdline(s: &str) -> Vec<String> {
+    s.split('\0')
+        .filter(|arg| !arg.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// clock ticks per second used by `utime`, `stime`, `starttime` (usually 100)
+pub fn clock_ticks() -> u64 {
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as u64
+    } else {
+        100
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Process {
+    pub pid: u32,
+    pub stat: ProcessStat,
+    pub status: ProcessStatus,
+    /// `cmdline` arguments
+    pub cmdline: Vec<String>,
+    /// `io` counters, `None` if not readable
+    pub io: Option<ProcessIo>,
+    /// `io` exists, but is not readable without privileges (other users' processes)
+    pub io_permission_denied: bool,
+}
+
+impl Process {
+    // get process by given path (e.g. `/proc/1`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let pid = path
+            .file_name()
+            .and_then(|p| p.to_str())
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "process: invalid pid"))?;
+
+        let (io, io_permission_denied) = match read_to_string(path.join(PROCESS_IO)) {
+            Ok(s) => (s.parse().ok(), false),
+            Err(e) => (None, e.kind() == ErrorKind::PermissionDenied),
+        };
+
+        Ok(Process {
+            pid,
+            stat: get_string_from_file(path.join(PROCESS_STAT))?.parse()?,
+            status: get_string_from_file(path.join(PROCESS_STATUS))?.parse()?,
+            // NOTE: not readable for some processes (e.g. hidepid), not an error
+            cmdline: get_string_from_file(path.join(PROCESS_CMDLINE))
+                .map(|s| parse_cmdline(&s))
+                .unwrap_or_default(),
+            io,
+            io_permission_denied,
+        })
+    }
+
+    /// kernel thread (e.g. `kworker/0:1`, `ksoftirqd/0`)
+    pub fn is_kernel_thread(&self) -> bool {
+        self.stat.flags & PF_KTHREAD != 0
+    }
+
+    /// same process in two snapshots, pids are reused
+    pub fn is_same(&self, other: &Process) -> bool {
+        self.pid == other.pid && self.stat.starttime == other.stat.starttime
+    }
+
+    /// CPU usage between two snapshots as percent of one CPU (may exceed 100 % for threads)
+    /// NOTE: all CPU time counts for processes started after the first snapshot
+    pub fn get_cpu_percent(&self, start: Option<&Process>, dt: u64) -> f64 {
+        let end = self.stat.utime + self.stat.stime;
+        let start = match start.filter(|start| self.is_same(start)) {
+            Some(start) => start.stat.utime + start.stat.stime,
+            None => 0,
+        };
+
+        end.saturating_sub(start) as f64 / (clock_ticks() * dt) as f64 * 100.0
+    }
+
+    /// I/O rates between two snapshots, `None` if `io` is not readable
+    /// NOTE: all I/O counts for processes started after the first snapshot
+    pub fn get_io_rate(&self, start: Option<&Process>, dt: u64) -> Option<ProcessIoRate> {
+        let end = self.io?;
+        let start = match start.filter(|start| self.is_same(start)) {
+            Some(start) => start.io?,
+            None => ProcessIo::default(),
+        };
 
-#[derive(Debug, Default)]
+        Some(end.get_rate(&start, dt))
+    }
+
+    /// `smaps_rollup`, `None` if not readable (e.g. other users' processes, kernel < 4.14)
+    /// NOTE: read on demand, expensive compared to `stat` and `status`
+    pub fn get_smaps_rollup(&self) -> Option<SmapsRollup> {
+        let path = Path::new(PROC).join(self.pid.to_string());
+        get_string_from_file(path.join(PROCESS_SMAPS_ROLLUP))
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    /// command line or `[comm]` for kernel threads and processes without one (like `ps`)
+    /// NOTE: control chars in arguments (e.g. `\n`) are replaced by spaces
+    pub fn get_command(&self) -> String {
+        match self.is_kernel_thread() || self.cmdline.is_empty() {
+            true => format!("[{}]", self.stat.comm),
+            false => self
+                .cmdline
+                .join(" ")
+                .replace(|c: char| c.is_control(), " "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ProcList {
-    pub processes: Vec<Proc>,
+    /// `BTreeMap<pid, Process{}>`
+    pub processes: BTreeMap<u32, Process>,
+}
+
+impl Deref for ProcList {
+    type Target = BTreeMap<u32, Process>;
+
+    fn deref(&self) -> &BTreeMap<u32, Process> {
+        &self.processes
+    }
+}
+
+impl DerefMut for ProcList {
+    fn deref_mut(&mut self) -> &mut BTreeMap<u32, Process> {
+        &mut self.processes
+    }
 }
 
 impl ProcList {
     pub fn get() -> Result<Self, Error> {
         let mut ps = ProcList::default();
 
-        if let Ok(dir) = std::fs::read_dir(PROC) {
-            for entry in dir.flatten() {
-                let mut path = entry.path().clone();
-
-                if let Some(id) = path
-                    .file_name()
-                    .and_then(|p| p.to_str())
-                    .and_then(|s| s.parse().ok())
-                {
-                    // let name = entry.path().to_string_lossy().to_string();
-                    path.push("cmdline");
-                    if let Ok(s) = get_string_from_file(path) {
-                        let cmdline: Vec<&str> = s.splitn(2, '\0').collect();
-                        if let Some(name) = cmdline.get(0) {
-                            let proc = Proc {
-                                id,
-                                name: name.to_string(),
-                            };
-
-                            ps.processes.push(proc);
-                        }
-                    }
-                }
+        for entry in std::fs::read_dir(PROC)?.flatten() {
+            // numeric directories only (e.g. `/proc/1`, not `/proc/self`)
+            if !entry
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|b| b.is_ascii_digit())
+            {
+                continue;
+            }
+            if let Ok(process) = Process::get(entry.path()) {
+                ps.insert(process.pid, process);
             }
         }
 
         Ok(ps)
     }
+
+    /// child pids by parent pid (`BTreeMap<ppid, Vec<pid>>`)
+    pub fn get_children(&self) -> BTreeMap<u32, Vec<u32>> {
+        let mut children: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for process in self.values() {
+            children
+                .entry(process.stat.ppid)
+                .or_default()
+                .push(process.pid);
+        }
+        children
+    }
+
+    /// processes without parent in list (e.g. `init`, `kthreadd`, processes of other pid namespaces)
+    pub fn get_roots(&self) -> Vec<u32> {
+        self.values()
+            .filter(|process| !self.contains_key(&process.stat.ppid))
+            .map(|process| process.pid)
+            .collect()
+    }
+}
+
+#[test]
+fn process_stat_from_str_test() {
+    let s = "1234 (my (weird) app) S 1 1234 1234 0 -1 4194560 1500 0 3 0 250 75 0 0 20 0 4 0 12345 104857600 2560 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 2 0 0 0 0 0";
+    let stat: ProcessStat = s.parse().unwrap();
+    assert_eq!(stat.comm, "my (weird) app");
+    assert_eq!(stat.state, 'S');
+    assert_eq!(stat.ppid, 1);
+    assert_eq!(stat.majflt, 3);
+    assert_eq!(stat.utime, 250);
+    assert_eq!(stat.stime, 75);
+    assert_eq!(stat.priority, 20);
+    assert_eq!(stat.nice, 0);
+    assert_eq!(stat.num_threads, 4);
+    assert_eq!(stat.starttime, 12345);
+    assert_eq!(stat.vsize, Bytes(104857600));
+    assert_eq!(stat.rss, Bytes::from_pages(2560));
+    assert_eq!(stat.processor, 2);
+
+    // kernel thread
+    let s = "2 (kthreadd) S 0 0 0 0 -1 2129984 0 0 0 0 0 0 0 0 20 0 1 0 2 0 0 18446744073709551615 0 0 0 0 0 0 0 2147483647 0 0 0 0 0 0 0 0 0 0";
+    let process = Process {
+        stat: s.parse().unwrap(),
+        ..Default::default()
+    };
+    assert!(process.is_kernel_thread());
+    assert_eq!(process.get_command(), "[kthreadd]");
+
+    assert!("1234 my app S".parse::<ProcessStat>().is_err());
+}
+
+#[test]
+fn process_get_cpu_percent_test() {
+    let start = Process {
+        pid: 42,
+        stat: ProcessStat {
+            utime: 100,
+            stime: 50,
+            starttime: 1000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let end = Process {
+        stat: ProcessStat {
+            utime: 100 + clock_ticks(),
+            stime: 50 + clock_ticks(),
+            ..start.stat.clone()
+        },
+        ..start.clone()
+    };
+    assert_eq!(end.get_cpu_percent(Some(&start), 2), 100.0);
+
+    // pid reused by a new process
+    let reused = Process {
+        stat: ProcessStat {
+            starttime: 2000,
+            ..end.stat.clone()
+        },
+        ..end.clone()
+    };
+    assert!(reused.get_cpu_percent(Some(&start), 2) > 100.0);
+}
+
+#[test]
+fn process_status_from_str_test() {
+    let s = "Name:\tfirefox
+State:\tS (sleeping)
+Pid:\t4242
+PPid:\t1
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t100\t100\t100\t100
+VmRSS:\t  524288 kB
+VmSwap:\t    1024 kB
+Threads:\t87
+voluntary_ctxt_switches:\t1500
+nonvoluntary_ctxt_switches:\t42
+";
+    let status: ProcessStatus = s.parse().unwrap();
+    assert_eq!(
+        status,
+        ProcessStatus {
+            uid: 1000,
+            gid: 100,
+            threads: 87,
+            vm_rss: Bytes::from_kib(524288),
+            vm_swap: Bytes::from_kib(1024),
+            voluntary_ctxt_switches: 1500,
+            nonvoluntary_ctxt_switches: 42,
+        }
+    );
+    assert_eq!(
+        parse_cmdline("/usr/bin/firefox\0--new-window\0"),
+        vec!["/usr/bin/firefox", "--new-window"]
+    );
 }
 
 #[test]
 fn get_proc_list_test() {
     let ps = ProcList::get().unwrap();
-    dbg!(ps);
+    assert!(ps.contains_key(&std::process::id()));
+    // kernel threads included (e.g. `kthreadd`), if visible in this pid namespace
+    for process in ps.values().filter(|p| p.is_kernel_thread()).take(3) {
+        println!("{} {}", process.pid, process.get_command());
+    }
+}
+
+#[test]
+fn proc_list_tree_test() {
+    let mut ps = ProcList::default();
+    for (pid, ppid) in [(1, 0), (2, 0), (10, 1), (11, 10), (12, 10), (20, 2)] {
+        let mut process = Process {
+            pid,
+            ..Default::default()
+        };
+        process.stat.ppid = ppid;
+        ps.insert(pid, process);
+    }
+
+    assert_eq!(ps.get_roots(), vec![1, 2]);
+    let children = ps.get_children();
+    assert_eq!(children.get(&10), Some(&vec![11, 12]));
+    assert_eq!(children.get(&2), Some(&vec![20]));
+    assert_eq!(children.get(&11), None);
 }
@@ -6,10 +6,23 @@
 /// /sys/class/dmi/id/board_name
 /// /sys/class/dmi/id/board_vendor
 /// /sys/class/dmi/id/board_version
+/// /sys/class/dmi/id/product_name
+/// /sys/class/dmi/id/product_version
+/// /sys/class/dmi/id/product_family
+/// /sys/class/dmi/id/sys_vendor
+/// /sys/class/dmi/id/chassis_type
+/// /sys/class/dmi/id/chassis_vendor
+/// /sys/class/dmi/id/product_serial (root only)
+/// /sys/class/dmi/id/product_uuid (root only)
+/// /sys/class/dmi/id/board_serial (root only)
+/// /sys/class/dmi/id/chassis_serial (root only)
 /// ```
+///
+/// NOTE: any file may be missing (e.g. VMs, ARM boards without DMI, see `DeviceTree`)
 use std::io::Error;
+use std::path::Path;
 
-use crate::{get_string_from_file, ICON_BIOS, ICON_MOTHERBOARD};
+use crate::get_string_from_path;
 
 const SYS_CLASS_DMI: &str = "/sys/class/dmi/id/";
 const BIOS_VERSION: &str = "bios_version";
@@ -18,35 +31,132 @@ const BIOS_VENDOR: &str = "bios_vendor";
 const BOARD_NAME: &str = "board_name";
 const BOARD_VENDOR: &str = "board_vendor";
 const BOARD_VERSION: &str = "board_version";
+const PRODUCT_NAME: &str = "product_name";
+const PRODUCT_VERSION: &str = "product_version";
+const PRODUCT_FAMILY: &str = "product_family";
+const SYS_VENDOR: &str = "sys_vendor";
+const CHASSIS_TYPE: &str = "chassis_type";
+const CHASSIS_VENDOR: &str = "chassis_vendor";
+const PRODUCT_SERIAL: &str = "product_serial";
+const PRODUCT_UUID: &str = "product_uuid";
+const BOARD_SERIAL: &str = "board_serial";
+const CHASSIS_SERIAL: &str = "chassis_serial";
+
+/// placeholders left by vendors in unset DMI strings
+const DMI_PLACEHOLDERS: [&str; 6] = [
+    "To Be Filled By O.E.M.",
+    "To be filled by O.E.M.",
+    "Default string",
+    "Not Specified",
+    "System Product Name",
+    "System manufacturer",
+];
 
 // DMI (Direct Media Interface) south north bridge bus
 // get strings from files from `/sys/class/dmi/id/*`
 #[derive(Debug, Default)]
 pub struct DmiInfo {
     /// `bios_vendor`
-    pub bios_vendor: String,
+    pub bios_vendor: Option<String>,
     /// `bios_version`
-    pub bios_version: String,
+    pub bios_version: Option<String>,
     /// `bios_date`
-    pub bios_date: String,
+    pub bios_date: Option<String>,
     /// `board_name`
-    pub board_name: String,
+    pub board_name: Option<String>,
     /// `board_vendor`
-    pub board_vendor: String,
+    pub board_vendor: Option<String>,
     /// `board_version`
-    pub board_version: String,
+    pub board_version: Option<String>,
+    /// `product_name` (e.g. `ThinkPad T480`, `KVM`)
+    pub product_name: Option<String>,
+    /// `product_version`
+    pub product_version: Option<String>,
+    /// `product_family` (e.g. `ThinkPad T480`)
+    pub product_family: Option<String>,
+    /// `sys_vendor` (e.g. `LENOVO`, `QEMU`)
+    pub sys_vendor: Option<String>,
+    /// `chassis_type`, see `chassis_type_to_str()`, `chassis_kind_to_str()`
+    pub chassis_type: Option<u8>,
+    /// `chassis_vendor`
+    pub chassis_vendor: Option<String>,
+    /// `product_serial` (root only)
+    pub product_serial: Option<String>,
+    /// `product_uuid` (root only)
+    pub product_uuid: Option<String>,
+    /// `board_serial` (root only)
+    pub board_serial: Option<String>,
+    /// `chassis_serial` (root only)
+    pub chassis_serial: Option<String>,
+}
+
+impl DmiInfo {
+    /// DMI info is available at all
+    pub fn is_empty(&self) -> bool {
+        self.board_name.is_none() && self.sys_vendor.is_none() && self.bios_vendor.is_none()
+    }
+}
+
+/// get DMI string, `None` if file is missing, not readable, blank or a vendor placeholder
+pub fn get_dmi_string(path: &Path, file: &str) -> Option<String> {
+    get_string_from_path(path, file)
+        .filter(|s| !s.is_empty() && !DMI_PLACEHOLDERS.contains(&s.as_str()))
 }
 
 // get bios and board info
 pub fn get_dmi_info() -> Result<DmiInfo, Error> {
-    let mut dmi_info = DmiInfo::default();
+    Ok(get_dmi_info_from_path(Path::new(SYS_CLASS_DMI)))
+}
+
+// get bios and board info from given dir (e.g. `/sys/class/dmi/id`)
+pub fn get_dmi_info_from_path(path: &Path) -> DmiInfo {
+    DmiInfo {
+        bios_vendor: get_dmi_string(path, BIOS_VENDOR),
+        bios_version: get_dmi_string(path, BIOS_VERSION),
+        bios_date: get_dmi_string(path, BIOS_DATE),
+        board_name: get_dmi_string(path, BOARD_NAME),
+        board_vendor: get_dmi_string(path, BOARD_VENDOR),
+        board_version: get_dmi_string(path, BOARD_VERSION),
+        product_name: get_dmi_string(path, PRODUCT_NAME),
+        product_version: get_dmi_string(path, PRODUCT_VERSION),
+        product_family: get_dmi_string(path, PRODUCT_FAMILY),
+        sys_vendor: get_dmi_string(path, SYS_VENDOR),
+        chassis_type: get_dmi_string(path, CHASSIS_TYPE).and_then(|s| s.parse().ok()),
+        chassis_vendor: get_dmi_string(path, CHASSIS_VENDOR),
+        product_serial: get_dmi_string(path, PRODUCT_SERIAL),
+        product_uuid: get_dmi_string(path, PRODUCT_UUID),
+        board_serial: get_dmi_string(path, BOARD_SERIAL),
+        chassis_serial: get_dmi_string(path, CHASSIS_SERIAL),
+    }
+}
+
+/// decode chassis type into a coarse class (e.g. `9` Laptop, `10` Notebook -> `laptop`)
+pub fn chassis_kind_to_str(chassis_type: u8) -> &'static str {
+    match chassis_type {
+        0x03..=0x07 | 0x0d | 0x0f | 0x10 | 0x18 => "desktop",
+        0x08..=0x0a | 0x0e | 0x1f | 0x20 => "laptop",
+        0x0b | 0x1e => "tablet",
+        0x11 | 0x17 | 0x19 | 0x1c | 0x1d => "server",
+        0x21..=0x24 => "embedded",
+        _ => "other",
+    }
+}
+
+#[test]
+fn get_dmi_info_from_path_test() {
+    let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/dmi");
 
-    dmi_info.bios_vendor = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BIOS_VENDOR))?;
-    dmi_info.bios_version = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BIOS_VERSION))?;
-    dmi_info.bios_date = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BIOS_DATE))?;
-    dmi_info.board_name = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BOARD_NAME))?;
-    dmi_info.board_vendor = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BOARD_VENDOR))?;
-    dmi_info.board_version = get_string_from_file(format!("{}{}", SYS_CLASS_DMI, BOARD_VERSION))?;
+    // no bios files, serials not readable
+    let dmi = get_dmi_info_from_path(&testdata.join("thinkpad-t480"));
+    assert_eq!(dmi.sys_vendor.as_deref(), Some("LENOVO"));
+    assert_eq!(dmi.product_name.as_deref(), Some("20L5CTO1WW"));
+    assert_eq!(dmi.product_family.as_deref(), Some("ThinkPad T480"));
+    assert_eq!(dmi.chassis_type, Some(10));
+    assert_eq!(dmi.board_version, None);
+    assert_eq!(dmi.bios_vendor, None);
+    assert_eq!(dmi.product_uuid, None);
+    assert!(!dmi.is_empty());
+    assert_eq!(chassis_kind_to_str(10), "laptop");
 
-    Ok(dmi_info)
+    assert!(get_dmi_info_from_path(&testdata.join("missing")).is_empty());
 }
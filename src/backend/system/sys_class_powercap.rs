@@ -0,0 +1,154 @@
+/// get info from sysfs
+/// ```text
+/// /sys/class/powercap/intel-rapl:<N>/name
+///                                   /energy_uj
+///                                   /max_energy_range_uj
+/// /sys/class/powercap/intel-rapl:<N>:<M>/...
+/// ```
+///
+/// RAPL (Running Average Power Limit) zones (e.g. `package-0`, `core`, `uncore`, `dram`)
+/// NOTE: `energy_uj` is readable by root only on most kernels (CVE-2020-8694)
+/// INFO: [kernel.org](https://docs.kernel.org/power/powercap/powercap.html)
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::{Error, ErrorKind};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use crate::get_string_from_path;
+
+pub const SYS_CLASS_POWERCAP: &str = "/sys/class/powercap";
+/// `/sys/class/powercap/intel-rapl:<N>` (AMD CPUs are exposed as `intel-rapl` too)
+pub(crate) const POWERCAP_INTEL_RAPL: &str = "intel-rapl:";
+/// `/sys/class/powercap/amd-rapl:<N>`
+pub(crate) const POWERCAP_AMD_RAPL: &str = "amd-rapl:";
+/// `/sys/class/powercap/<ZONE>/name`
+pub(crate) const POWERCAP_NAME: &str = "name";
+/// `/sys/class/powercap/<ZONE>/energy_uj`
+pub(crate) const POWERCAP_ENERGY_UJ: &str = "energy_uj";
+/// `/sys/class/powercap/<ZONE>/max_energy_range_uj`
+pub(crate) const POWERCAP_MAX_ENERGY_RANGE_UJ: &str = "max_energy_range_uj";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PowercapZone {
+    /// `name` (e.g. `package-0`, `core`, `dram`)
+    pub name: String,
+    /// `energy_uj` energy counter (uJ), `None` if not readable
+    pub energy_uj: Option<u64>,
+    /// `max_energy_range_uj` counter wraps at this value (uJ)
+    pub max_energy_range_uj: Option<u64>,
+    /// `energy_uj` exists, but is not readable without privileges
+    pub permission_denied: bool,
+}
+
+impl PowercapZone {
+    // get zone by given path (e.g. `/sys/class/powercap/intel-rapl:0`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let mut zone = PowercapZone {
+            name: get_string_from_path(path, POWERCAP_NAME).unwrap_or_default(),
+            max_energy_range_uj: get_string_from_path(path, POWERCAP_MAX_ENERGY_RANGE_UJ)
+                .and_then(|s| s.parse().ok()),
+            ..Default::default()
+        };
+
+        match read_to_string(path.join(POWERCAP_ENERGY_UJ)) {
+            Ok(s) => zone.energy_uj = s.trim().parse().ok(),
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => zone.permission_denied = true,
+            Err(e) => return Err(e),
+        }
+
+        Ok(zone)
+    }
+
+    /// average power between two snapshots (W), counter wrap around `max_energy_range_uj` handled
+    pub fn get_power(&self, start: &PowercapZone, dt: u64) -> Option<f64> {
+        let end = self.energy_uj?;
+        let start = start.energy_uj?;
+
+        let delta = if end >= start {
+            end - start
+        } else {
+            self.max_energy_range_uj?.saturating_sub(start) + end
+        };
+
+        Some(delta as f64 / (dt as f64 * 1_000_000.0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PowercapZones {
+    /// `BTreeMap<zone directory, PowercapZone{}>` (e.g. `intel-rapl:0`, `intel-rapl:0:0`)
+    pub zones: BTreeMap<String, PowercapZone>,
+}
+
+impl Deref for PowercapZones {
+    type Target = BTreeMap<String, PowercapZone>;
+
+    fn deref(&self) -> &BTreeMap<String, PowercapZone> {
+        &self.zones
+    }
+}
+
+impl DerefMut for PowercapZones {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, PowercapZone> {
+        &mut self.zones
+    }
+}
+
+impl PowercapZones {
+    // NOTE: empty if there is no powercap driver (e.g. VMs, ARM)
+    pub fn get() -> Result<Self, Error> {
+        let mut zones = PowercapZones::default();
+
+        if let Ok(dir) = std::fs::read_dir(SYS_CLASS_POWERCAP) {
+            for entry in dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with(POWERCAP_INTEL_RAPL) || name.starts_with(POWERCAP_AMD_RAPL) {
+                    if let Ok(zone) = PowercapZone::get(entry.path()) {
+                        zones.insert(name, zone);
+                    }
+                }
+            }
+        }
+
+        Ok(zones)
+    }
+
+    /// zones exist, but counters are readable by root only
+    pub fn is_permission_denied(&self) -> bool {
+        self.values().any(|zone| zone.permission_denied)
+    }
+}
+
+#[test]
+fn powercap_zone_get_power_test() {
+    let start = PowercapZone {
+        energy_uj: Some(262_000_000),
+        max_energy_range_uj: Some(262_143_328_850),
+        ..Default::default()
+    };
+    let end = PowercapZone {
+        energy_uj: Some(282_000_000),
+        ..start.clone()
+    };
+    assert_eq!(end.get_power(&start, 2), Some(10.0));
+
+    // wrap around
+    let start = PowercapZone {
+        energy_uj: Some(262_143_328_850 - 5_000_000),
+        ..start
+    };
+    let end = PowercapZone {
+        energy_uj: Some(5_000_000),
+        ..start.clone()
+    };
+    assert_eq!(end.get_power(&start, 1), Some(10.0));
+
+    // not readable
+    let denied = PowercapZone {
+        permission_denied: true,
+        ..Default::default()
+    };
+    assert_eq!(denied.get_power(&start, 1), None);
+}
@@ -0,0 +1,160 @@
+/// get info from sysfs
+/// ```text
+/// /sys/devices/system/cpu/vulnerabilities/*
+/// ```
+///
+/// INFO: [kernel.org](https://docs.kernel.org/admin-guide/hw-vuln/index.html)
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+use crate::get_string_from_file;
+
+pub const SYS_CPU_VULNERABILITIES: &str = "/sys/devices/system/cpu/vulnerabilities";
+
+/// kernel command line option setting all mitigations (e.g. `mitigations=auto,nosmt`)
+pub const MITIGATIONS_OPTION: &str = "mitigations";
+/// kernel command line option disabling SMT
+pub const NOSMT_OPTION: &str = "nosmt";
+/// kernel command line options affecting single mitigations
+pub const MITIGATION_OPTIONS: [&str; 10] = [
+    "nospectre_v1",
+    "nospectre_v2",
+    "spectre_v2",
+    "spectre_v2_user",
+    "spec_store_bypass_disable",
+    "pti",
+    "nopti",
+    "l1tf",
+    "mds",
+    "retbleed",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vulnerability {
+    /// `Not affected`
+    NotAffected,
+    /// `Mitigation: <how>` (e.g. `Mitigation: TSX disabled`)
+    Mitigated(String),
+    /// `Vulnerable`, `Vulnerable: <details>`
+    Vulnerable(String),
+    /// anything else (e.g. `Unknown: Dependent on hypervisor status`)
+    Unknown(String),
+}
+
+impl FromStr for Vulnerability {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // NOTE: prefixed (e.g. `KVM: Mitigation: VMX disabled`, `KVM: Vulnerable` of itlb_multihit)
+        let status = s.strip_prefix("KVM: ").unwrap_or(s);
+        // case differs between kernel versions (e.g. `Processor vulnerable` of l1tf)
+        let lower = status.to_lowercase();
+
+        let vulnerability = if lower.starts_with("not affected") {
+            Vulnerability::NotAffected
+        } else if lower.starts_with("vulnerable") || lower.starts_with("processor vulnerable") {
+            let details = status.split_once(": ").map(|(_, d)| d).unwrap_or_default();
+            Vulnerability::Vulnerable(details.to_string())
+        } else if lower.starts_with("mitigation: ") {
+            Vulnerability::Mitigated(status["Mitigation: ".len()..].to_string())
+        } else {
+            Vulnerability::Unknown(s.to_string())
+        };
+
+        Ok(vulnerability)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpuVulnerabilities {
+    /// `BTreeMap<vulnerability, Vulnerability>` (e.g. `spectre_v2`, `meltdown`)
+    pub vulnerabilities: BTreeMap<String, Vulnerability>,
+}
+
+impl Deref for CpuVulnerabilities {
+    type Target = BTreeMap<String, Vulnerability>;
+
+    fn deref(&self) -> &BTreeMap<String, Vulnerability> {
+        &self.vulnerabilities
+    }
+}
+
+impl DerefMut for CpuVulnerabilities {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, Vulnerability> {
+        &mut self.vulnerabilities
+    }
+}
+
+impl CpuVulnerabilities {
+    // NOTE: empty on kernels < 4.15 or architectures without the directory
+    pub fn get() -> Result<Self, Error> {
+        let mut vulnerabilities = CpuVulnerabilities::default();
+
+        if let Ok(dir) = std::fs::read_dir(SYS_CPU_VULNERABILITIES) {
+            for entry in dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                // unreadable entries are reported, not the whole report failed
+                let vulnerability = match get_string_from_file(entry.path()) {
+                    Ok(s) => s.parse()?,
+                    Err(e) => Vulnerability::Unknown(format!("Not readable: {}", e)),
+                };
+                vulnerabilities.insert(name, vulnerability);
+            }
+        }
+
+        Ok(vulnerabilities)
+    }
+}
+
+#[test]
+fn vulnerability_from_str_test() {
+    assert_eq!(
+        "Not affected".parse::<Vulnerability>().unwrap(),
+        Vulnerability::NotAffected
+    );
+    assert_eq!(
+        "Mitigation: TSX disabled".parse::<Vulnerability>().unwrap(),
+        Vulnerability::Mitigated("TSX disabled".to_string())
+    );
+    assert_eq!(
+        "KVM: Mitigation: VMX disabled"
+            .parse::<Vulnerability>()
+            .unwrap(),
+        Vulnerability::Mitigated("VMX disabled".to_string())
+    );
+    assert_eq!(
+        "Vulnerable: Clear CPU buffers attempted, no microcode"
+            .parse::<Vulnerability>()
+            .unwrap(),
+        Vulnerability::Vulnerable("Clear CPU buffers attempted, no microcode".to_string())
+    );
+    assert_eq!(
+        "Vulnerable".parse::<Vulnerability>().unwrap(),
+        Vulnerability::Vulnerable(String::new())
+    );
+    assert_eq!(
+        "KVM: Vulnerable".parse::<Vulnerability>().unwrap(),
+        Vulnerability::Vulnerable(String::new())
+    );
+    assert_eq!(
+        "Processor vulnerable".parse::<Vulnerability>().unwrap(),
+        Vulnerability::Vulnerable(String::new())
+    );
+    assert_eq!(
+        "Mitigation: PTE Inversion; VMX: conditional cache flushes, SMT vulnerable"
+            .parse::<Vulnerability>()
+            .unwrap(),
+        Vulnerability::Mitigated(
+            "PTE Inversion; VMX: conditional cache flushes, SMT vulnerable".to_string()
+        )
+    );
+    assert_eq!(
+        "Unknown: Dependent on hypervisor status"
+            .parse::<Vulnerability>()
+            .unwrap(),
+        Vulnerability::Unknown("Unknown: Dependent on hypervisor status".to_string())
+    );
+}
@@ -0,0 +1,162 @@
+/// get info from sysfs
+/// ```text
+/// /sys/devices/system/cpu/cpu*/cpuidle/state*/name
+///                                             /desc
+///                                             /latency
+///                                             /usage
+///                                             /time
+///                                             /disable
+/// ```
+///
+/// INFO: [kernel.org](https://docs.kernel.org/admin-guide/pm/cpuidle.html)
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+use crate::{bool_from_str, get_string_from_path};
+
+pub const SYS_DEVICES_SYSTEM_CPU: &str = "/sys/devices/system/cpu";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle`
+pub(crate) const CPUIDLE: &str = "cpuidle";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>`
+pub(crate) const CPUIDLE_STATE: &str = "state";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/name`
+pub(crate) const CPUIDLE_NAME: &str = "name";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/desc`
+pub(crate) const CPUIDLE_DESC: &str = "desc";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/latency`
+pub(crate) const CPUIDLE_LATENCY: &str = "latency";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/usage`
+pub(crate) const CPUIDLE_USAGE: &str = "usage";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/time`
+pub(crate) const CPUIDLE_TIME: &str = "time";
+/// `/sys/devices/system/cpu/cpu<N>/cpuidle/state<N>/disable`
+pub(crate) const CPUIDLE_DISABLE: &str = "disable";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpuIdleState {
+    /// `name` (e.g. `POLL`, `C1`, `C6`)
+    pub name: String,
+    /// `desc` (e.g. `MWAIT 0x20`)
+    pub desc: String,
+    /// `latency` exit latency (us)
+    pub latency: u64,
+    /// `usage` number of times the state was entered
+    pub usage: u64,
+    /// `time` total time spent in the state (us)
+    pub time: u64,
+    /// `disable` state disabled
+    pub disable: Option<bool>,
+}
+
+impl CpuIdleState {
+    // get state info by given path (e.g. `/sys/devices/system/cpu/cpu0/cpuidle/state1`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let number = |file| {
+            get_string_from_path(path, file)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_default()
+        };
+
+        Ok(CpuIdleState {
+            name: get_string_from_path(path, CPUIDLE_NAME).unwrap_or_default(),
+            desc: get_string_from_path(path, CPUIDLE_DESC).unwrap_or_default(),
+            latency: number(CPUIDLE_LATENCY),
+            usage: number(CPUIDLE_USAGE),
+            time: number(CPUIDLE_TIME),
+            disable: get_string_from_path(path, CPUIDLE_DISABLE).and_then(|s| bool_from_str(&s)),
+        })
+    }
+
+    /// time spent in the state between two snapshots as percent of `dt` seconds
+    pub fn get_residency(&self, start: &CpuIdleState, dt: u64) -> f64 {
+        let delta = self.time.saturating_sub(start.time) as f64;
+        delta / (dt as f64 * 1_000_000.0) * 100.0
+    }
+}
+
+// idle states of a single CPU, ordered by state index (`state0`, `state1`, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpuIdle {
+    pub states: Vec<CpuIdleState>,
+}
+
+impl CpuIdle {
+    // get idle states by given cpu path (e.g. `/sys/devices/system/cpu/cpu0`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut states = BTreeMap::new();
+
+        for entry in std::fs::read_dir(path.as_ref().join(CPUIDLE))?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(i) = name
+                .strip_prefix(CPUIDLE_STATE)
+                .and_then(|i| i.parse::<usize>().ok())
+            {
+                states.insert(i, CpuIdleState::get(entry.path())?);
+            }
+        }
+
+        Ok(CpuIdle {
+            states: states.into_values().collect(),
+        })
+    }
+}
+
+// NOTE: empty if no cpuidle driver is loaded (e.g. some VMs)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CpuIdles {
+    /// `BTreeMap<cpu number, CpuIdle{}>`
+    pub cpus: BTreeMap<usize, CpuIdle>,
+}
+
+impl Deref for CpuIdles {
+    type Target = BTreeMap<usize, CpuIdle>;
+
+    fn deref(&self) -> &BTreeMap<usize, CpuIdle> {
+        &self.cpus
+    }
+}
+
+impl DerefMut for CpuIdles {
+    fn deref_mut(&mut self) -> &mut BTreeMap<usize, CpuIdle> {
+        &mut self.cpus
+    }
+}
+
+impl CpuIdles {
+    pub fn get() -> Result<Self, Error> {
+        let mut idles = CpuIdles::default();
+
+        // `cpu0`, `cpu1`, ... (skip `cpufreq`, `cpuidle`)
+        for entry in std::fs::read_dir(SYS_DEVICES_SYSTEM_CPU)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(cpu) = name.strip_prefix("cpu").and_then(|i| i.parse().ok()) {
+                if let Ok(idle) = CpuIdle::get(entry.path()) {
+                    idles.insert(cpu, idle);
+                }
+            }
+        }
+
+        Ok(idles)
+    }
+}
+
+#[test]
+fn cpuidle_get_test() {
+    let cpu = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/cpuidle/cpu0");
+
+    let idle = CpuIdle::get(&cpu).unwrap();
+    let names: Vec<&str> = idle.states.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["POLL", "C1", "C6"]);
+    assert_eq!(idle.states[0].disable, Some(true));
+    assert_eq!(idle.states[2].desc, "MWAIT 0x20");
+    assert_eq!(idle.states[2].latency, 85);
+    assert_eq!(idle.states[2].usage, 9377);
+    assert_eq!(idle.states[2].time, 1500000);
+    assert_eq!(idle.states[2].disable, Some(false));
+
+    let start = CpuIdleState::default();
+    assert_eq!(idle.states[1].get_residency(&start, 1), 50.0);
+}
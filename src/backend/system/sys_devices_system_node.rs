@@ -0,0 +1,176 @@
+/// get info from sysfs
+/// ```text
+/// /sys/devices/system/node/node*/meminfo
+///                               /numastat
+///                               /cpulist
+///                               /distance
+/// ```
+///
+/// NUMA (Non-Uniform Memory Access) nodes
+/// INFO: [kernel.org](https://www.kernel.org/doc/Documentation/ABI/stable/sysfs-devices-node)
+use std::collections::BTreeMap;
+use std::io::Error;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{get_string_from_path, parse_cpu_list, MemInfo};
+
+pub const SYS_DEVICES_SYSTEM_NODE: &str = "/sys/devices/system/node";
+/// `/sys/devices/system/node/node<N>`
+pub(crate) const NODE: &str = "node";
+/// `/sys/devices/system/node/node<N>/meminfo`
+pub(crate) const NODE_MEMINFO: &str = "meminfo";
+/// `/sys/devices/system/node/node<N>/numastat`
+pub(crate) const NODE_NUMASTAT: &str = "numastat";
+/// `/sys/devices/system/node/node<N>/cpulist`
+pub(crate) const NODE_CPULIST: &str = "cpulist";
+/// `/sys/devices/system/node/node<N>/distance`
+pub(crate) const NODE_DISTANCE: &str = "distance";
+
+// `numastat`, counters in pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NumaStat {
+    /// memory successfully allocated on this node as intended
+    pub numa_hit: u64,
+    /// memory allocated on this node despite the process preferring some different node
+    pub numa_miss: u64,
+    /// memory intended for this node, but actually allocated on some different node
+    pub numa_foreign: u64,
+    /// interleaved memory successfully allocated on this node
+    pub interleave_hit: u64,
+    /// memory allocated on this node while a process was running on it
+    pub local_node: u64,
+    /// memory allocated on this node while a process was running on some other node
+    pub other_node: u64,
+}
+
+impl FromStr for NumaStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numastat = NumaStat::default();
+
+        for line in s.lines() {
+            if let Some((key, value)) = line.split_once(' ') {
+                let value = value.trim().parse().unwrap_or_default();
+                match key {
+                    "numa_hit" => numastat.numa_hit = value,
+                    "numa_miss" => numastat.numa_miss = value,
+                    "numa_foreign" => numastat.numa_foreign = value,
+                    "interleave_hit" => numastat.interleave_hit = value,
+                    "local_node" => numastat.local_node = value,
+                    "other_node" => numastat.other_node = value,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(numastat)
+    }
+}
+
+/// parse node meminfo, same keys as `/proc/meminfo` with a prefix
+/// (e.g. `Node 0 MemTotal:        4947704 kB`)
+pub fn parse_node_meminfo(s: &str) -> Result<MemInfo, Error> {
+    let buf: Vec<&str> = s
+        .lines()
+        .map(|line| line.splitn(3, ' ').nth(2).unwrap_or_default().trim())
+        .collect();
+    buf.join("\n").parse()
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct NumaNode {
+    /// `meminfo` (e.g. `MemTotal`, `MemFree`, `FilePages`)
+    pub meminfo: MemInfo,
+    /// `numastat`
+    pub numastat: NumaStat,
+    /// `cpulist` CPUs belonging to this node
+    pub cpus: Vec<usize>,
+    /// `distance` relative distance to each node (e.g. `10 21`)
+    pub distance: Vec<u64>,
+}
+
+impl NumaNode {
+    // get node by given path (e.g. `/sys/devices/system/node/node0`)
+    pub fn get<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        Ok(NumaNode {
+            meminfo: parse_node_meminfo(
+                &get_string_from_path(path, NODE_MEMINFO).unwrap_or_default(),
+            )?,
+            numastat: get_string_from_path(path, NODE_NUMASTAT)
+                .unwrap_or_default()
+                .parse()?,
+            cpus: parse_cpu_list(&get_string_from_path(path, NODE_CPULIST).unwrap_or_default()),
+            distance: get_string_from_path(path, NODE_DISTANCE)
+                .unwrap_or_default()
+                .split_whitespace()
+                .filter_map(|d| d.parse().ok())
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct NumaNodes {
+    /// `BTreeMap<node number, NumaNode{}>`
+    pub nodes: BTreeMap<usize, NumaNode>,
+}
+
+impl Deref for NumaNodes {
+    type Target = BTreeMap<usize, NumaNode>;
+
+    fn deref(&self) -> &BTreeMap<usize, NumaNode> {
+        &self.nodes
+    }
+}
+
+impl DerefMut for NumaNodes {
+    fn deref_mut(&mut self) -> &mut BTreeMap<usize, NumaNode> {
+        &mut self.nodes
+    }
+}
+
+impl NumaNodes {
+    // NOTE: empty if kernel is built without `CONFIG_NUMA`
+    pub fn get() -> Result<Self, Error> {
+        let mut nodes = NumaNodes::default();
+
+        if let Ok(dir) = std::fs::read_dir(SYS_DEVICES_SYSTEM_NODE) {
+            for entry in dir.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if let Some(node) = name.strip_prefix(NODE).and_then(|n| n.parse().ok()) {
+                    nodes.insert(node, NumaNode::get(entry.path())?);
+                }
+            }
+        }
+
+        Ok(nodes)
+    }
+}
+
+#[test]
+fn numa_node_from_str_test() {
+    use crate::Bytes;
+
+    let meminfo = parse_node_meminfo(
+        "Node 0 MemTotal:        4947704 kB\n\
+         Node 0 MemFree:         3349004 kB\n\
+         Node 0 MemUsed:         1598700 kB",
+    )
+    .unwrap();
+    assert_eq!(meminfo.mem_total, Bytes::from_kib(4947704));
+    assert_eq!(meminfo.mem_free, Bytes::from_kib(3349004));
+    assert_eq!(meminfo.other.get("MemUsed"), Some(&1598700));
+
+    let numastat: NumaStat = "numa_hit 3807061\nnuma_miss 2\nnuma_foreign 3\nother_node 4"
+        .parse()
+        .unwrap();
+    assert_eq!(numastat.numa_hit, 3807061);
+    assert_eq!(numastat.numa_miss, 2);
+    assert_eq!(numastat.numa_foreign, 3);
+    assert_eq!(numastat.other_node, 4);
+}
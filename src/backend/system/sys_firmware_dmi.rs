@@ -0,0 +1,807 @@
+/// get info from sysfs
+/// ```text
+/// /sys/firmware/dmi/tables/smbios_entry_point
+/// /sys/firmware/dmi/tables/DMI
+/// ```
+///
+/// raw SMBIOS structures, each structure is a formatted area followed by a string set
+/// ```text
+/// type (1) length (1) handle (2) ...formatted area... "string 1\0string 2\0\0"
+/// ```
+///
+/// NOTE: both files are readable by root only
+/// INFO: [DMTF DSP0134](https://www.dmtf.org/standards/smbios)
+use std::io::{Error, ErrorKind};
+
+use crate::Bytes;
+
+pub const SYS_FIRMWARE_DMI_TABLES: &str = "/sys/firmware/dmi/tables";
+/// `/sys/firmware/dmi/tables/smbios_entry_point`
+pub(crate) const DMI_ENTRY_POINT: &str = "smbios_entry_point";
+/// `/sys/firmware/dmi/tables/DMI`
+pub(crate) const DMI_TABLE: &str = "DMI";
+
+/// 32-bit entry point anchor (SMBIOS 2.x)
+pub(crate) const SMBIOS2_ANCHOR: &[u8] = b"_SM_";
+/// 64-bit entry point anchor (SMBIOS 3.x)
+pub(crate) const SMBIOS3_ANCHOR: &[u8] = b"_SM3_";
+
+/// type 0, BIOS Information
+pub(crate) const SMBIOS_BIOS: u8 = 0;
+/// type 1, System Information
+pub(crate) const SMBIOS_SYSTEM: u8 = 1;
+/// type 2, Baseboard Information
+pub(crate) const SMBIOS_BASEBOARD: u8 = 2;
+/// type 3, System Enclosure or Chassis
+pub(crate) const SMBIOS_CHASSIS: u8 = 3;
+/// type 4, Processor Information
+pub(crate) const SMBIOS_PROCESSOR: u8 = 4;
+/// type 16, Physical Memory Array
+pub(crate) const SMBIOS_MEMORY_ARRAY: u8 = 16;
+/// type 17, Memory Device
+pub(crate) const SMBIOS_MEMORY_DEVICE: u8 = 17;
+/// type 19, Memory Array Mapped Address
+pub(crate) const SMBIOS_MEMORY_MAPPING: u8 = 19;
+/// type 127, End-of-Table
+pub(crate) const SMBIOS_END: u8 = 127;
+
+/// type 16 `Use`, system memory (other arrays are e.g. video or flash memory)
+pub(crate) const MEMORY_ARRAY_USE_SYSTEM: u8 = 0x03;
+
+/// single raw structure, formatted area (header included) and string set
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosStructure {
+    /// `Type`
+    pub kind: u8,
+    /// `Handle`
+    pub handle: u16,
+    /// formatted area, offsets as in the specification (`Type` at 0x00)
+    pub data: Vec<u8>,
+    /// string set, referenced by 1-based index from the formatted area
+    pub strings: Vec<String>,
+}
+
+impl SmbiosStructure {
+    pub fn byte(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+
+    pub fn word(&self, offset: usize) -> Option<u16> {
+        let b = self.data.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    pub fn dword(&self, offset: usize) -> Option<u32> {
+        let b = self.data.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub fn qword(&self, offset: usize) -> Option<u64> {
+        let b = self.data.get(offset..offset + 8)?;
+        let mut buf = [0; 8];
+        buf.copy_from_slice(b);
+        Some(u64::from_le_bytes(buf))
+    }
+
+    /// string referenced by the byte at given offset, `None` if index is 0 or string is blank
+    pub fn string(&self, offset: usize) -> Option<String> {
+        let index = self.byte(offset)? as usize;
+        let s = self.strings.get(index.checked_sub(1)?)?.trim();
+        match s.is_empty() {
+            true => None,
+            false => Some(s.to_string()),
+        }
+    }
+}
+
+/// split table into raw structures, stops at End-of-Table or at the first malformed structure
+pub fn parse_smbios_structures(table: &[u8]) -> Vec<SmbiosStructure> {
+    let mut structures = Vec::new();
+    let mut pos = 0;
+
+    while let Some(header) = table.get(pos..pos + 4) {
+        let length = header[1] as usize;
+        if length < 4 || pos + length > table.len() {
+            break;
+        }
+
+        // string set ends with double `\0` (`\0\0` only, if there are no strings)
+        let strings_start = pos + length;
+        let strings_end = match table[strings_start..].windows(2).position(|w| w == [0, 0]) {
+            Some(i) => strings_start + i,
+            None => break,
+        };
+
+        let strings = table[strings_start..strings_end]
+            .split(|b| *b == 0)
+            .filter(|s| !s.is_empty())
+            .map(|s| String::from_utf8_lossy(s).to_string())
+            .collect();
+
+        let structure = SmbiosStructure {
+            kind: header[0],
+            handle: u16::from_le_bytes([header[2], header[3]]),
+            data: table[pos..pos + length].to_vec(),
+            strings,
+        };
+        pos = strings_end + 2;
+
+        if structure.kind == SMBIOS_END {
+            break;
+        }
+        structures.push(structure);
+    }
+
+    structures
+}
+
+/// get SMBIOS version (`major`, `minor`) from entry point structure
+pub fn parse_smbios_version(entry_point: &[u8]) -> Option<(u8, u8)> {
+    if entry_point.starts_with(SMBIOS3_ANCHOR) {
+        Some((*entry_point.get(7)?, *entry_point.get(8)?))
+    } else if entry_point.starts_with(SMBIOS2_ANCHOR) {
+        Some((*entry_point.get(6)?, *entry_point.get(7)?))
+    } else {
+        None
+    }
+}
+
+/// type 0, BIOS Information
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosBios {
+    pub vendor: Option<String>,
+    pub version: Option<String>,
+    pub release_date: Option<String>,
+}
+
+impl From<&SmbiosStructure> for SmbiosBios {
+    fn from(s: &SmbiosStructure) -> Self {
+        SmbiosBios {
+            vendor: s.string(0x04),
+            version: s.string(0x05),
+            release_date: s.string(0x08),
+        }
+    }
+}
+
+/// type 1, System Information
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosSystem {
+    pub manufacturer: Option<String>,
+    pub product_name: Option<String>,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+    /// `None` if not present or not set (all bits 0 or 1)
+    pub uuid: Option<String>,
+    pub sku_number: Option<String>,
+    pub family: Option<String>,
+}
+
+/// format UUID, first three fields are little-endian (SMBIOS >= 2.6)
+pub fn smbios_uuid_to_string(b: &[u8]) -> Option<String> {
+    if b.len() != 16 || b.iter().all(|b| *b == 0) || b.iter().all(|b| *b == 0xff) {
+        return None;
+    }
+
+    Some(format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15]
+    ))
+}
+
+impl From<&SmbiosStructure> for SmbiosSystem {
+    fn from(s: &SmbiosStructure) -> Self {
+        SmbiosSystem {
+            manufacturer: s.string(0x04),
+            product_name: s.string(0x05),
+            version: s.string(0x06),
+            serial_number: s.string(0x07),
+            uuid: s.data.get(0x08..0x18).and_then(smbios_uuid_to_string),
+            sku_number: s.string(0x19),
+            family: s.string(0x1a),
+        }
+    }
+}
+
+/// type 2, Baseboard (or Module) Information
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosBaseboard {
+    pub manufacturer: Option<String>,
+    pub product_name: Option<String>,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+impl From<&SmbiosStructure> for SmbiosBaseboard {
+    fn from(s: &SmbiosStructure) -> Self {
+        SmbiosBaseboard {
+            manufacturer: s.string(0x04),
+            product_name: s.string(0x05),
+            version: s.string(0x06),
+            serial_number: s.string(0x07),
+        }
+    }
+}
+
+/// type 3, System Enclosure or Chassis
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosChassis {
+    pub manufacturer: Option<String>,
+    /// chassis type, see `chassis_type_to_str()`
+    pub chassis_type: u8,
+    pub version: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+impl From<&SmbiosStructure> for SmbiosChassis {
+    fn from(s: &SmbiosStructure) -> Self {
+        SmbiosChassis {
+            manufacturer: s.string(0x04),
+            // bit 7 is chassis lock
+            chassis_type: s.byte(0x05).unwrap_or_default() & 0x7f,
+            version: s.string(0x06),
+            serial_number: s.string(0x07),
+        }
+    }
+}
+
+/// decode chassis type, same values as `/sys/class/dmi/id/chassis_type`
+pub fn chassis_type_to_str(chassis_type: u8) -> &'static str {
+    match chassis_type {
+        0x01 => "Other",
+        0x03 => "Desktop",
+        0x04 => "Low Profile Desktop",
+        0x05 => "Pizza Box",
+        0x06 => "Mini Tower",
+        0x07 => "Tower",
+        0x08 => "Portable",
+        0x09 => "Laptop",
+        0x0a => "Notebook",
+        0x0b => "Hand Held",
+        0x0c => "Docking Station",
+        0x0d => "All in One",
+        0x0e => "Sub Notebook",
+        0x0f => "Space-saving",
+        0x10 => "Lunch Box",
+        0x11 => "Main Server Chassis",
+        0x12 => "Expansion Chassis",
+        0x13 => "SubChassis",
+        0x14 => "Bus Expansion Chassis",
+        0x15 => "Peripheral Chassis",
+        0x16 => "RAID Chassis",
+        0x17 => "Rack Mount Chassis",
+        0x18 => "Sealed-case PC",
+        0x19 => "Multi-system Chassis",
+        0x1a => "Compact PCI",
+        0x1b => "Advanced TCA",
+        0x1c => "Blade",
+        0x1d => "Blade Enclosure",
+        0x1e => "Tablet",
+        0x1f => "Convertible",
+        0x20 => "Detachable",
+        0x21 => "IoT Gateway",
+        0x22 => "Embedded PC",
+        0x23 => "Mini PC",
+        0x24 => "Stick PC",
+        _ => "Unknown",
+    }
+}
+
+/// type 4, Processor Information
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosProcessor {
+    /// socket designation (e.g. `CPU0`, `AM4`)
+    pub socket: Option<String>,
+    pub manufacturer: Option<String>,
+    pub version: Option<String>,
+    /// max speed supported by the board (MHz)
+    pub max_speed: Option<u16>,
+    /// speed at boot (MHz)
+    pub current_speed: Option<u16>,
+    /// core count (SMBIOS >= 2.5), `None` for unpopulated sockets
+    pub core_count: Option<u8>,
+    /// thread count (SMBIOS >= 2.5)
+    pub thread_count: Option<u8>,
+}
+
+impl From<&SmbiosStructure> for SmbiosProcessor {
+    fn from(s: &SmbiosStructure) -> Self {
+        let nonzero_word = |offset| s.word(offset).filter(|n| *n != 0);
+        let nonzero_byte = |offset| s.byte(offset).filter(|n| *n != 0);
+
+        SmbiosProcessor {
+            socket: s.string(0x04),
+            manufacturer: s.string(0x07),
+            version: s.string(0x10),
+            max_speed: nonzero_word(0x14),
+            current_speed: nonzero_word(0x16),
+            core_count: nonzero_byte(0x23),
+            thread_count: nonzero_byte(0x25),
+        }
+    }
+}
+
+/// type 16, Physical Memory Array
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosMemoryArray {
+    pub handle: u16,
+    /// `Use` (e.g. `0x03` system memory, `0x04` video memory)
+    pub usage: u8,
+    /// maximum memory capacity, `None` if unknown
+    pub max_capacity: Option<Bytes>,
+    /// number of memory slots
+    pub slots: u16,
+}
+
+impl From<&SmbiosStructure> for SmbiosMemoryArray {
+    fn from(s: &SmbiosStructure) -> Self {
+        let max_capacity = match s.dword(0x07) {
+            // `Extended Maximum Capacity` in bytes (SMBIOS >= 2.7)
+            Some(0x8000_0000) => s.qword(0x0f).map(Bytes),
+            Some(kib) => Some(Bytes::from_kib(kib as u64)),
+            None => None,
+        };
+
+        SmbiosMemoryArray {
+            handle: s.handle,
+            usage: s.byte(0x05).unwrap_or_default(),
+            max_capacity,
+            slots: s.word(0x0d).unwrap_or_default(),
+        }
+    }
+}
+
+/// type 17, Memory Device (memory module or empty slot)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosMemoryDevice {
+    pub handle: u16,
+    /// handle of the `SmbiosMemoryArray` the device belongs to
+    pub array_handle: u16,
+    /// module size, `None` if slot is empty or size is unknown
+    pub size: Option<Bytes>,
+    /// form factor, see `memory_form_factor_to_str()`
+    pub form_factor: u8,
+    /// slot (e.g. `DIMM_A1`, `ChannelA-DIMM0`)
+    pub locator: Option<String>,
+    /// bank (e.g. `BANK 0`, `P0 CHANNEL A`)
+    pub bank_locator: Option<String>,
+    /// memory type, see `memory_type_to_str()`
+    pub memory_type: u8,
+    /// maximum speed of the module (MT/s)
+    pub speed: Option<u32>,
+    /// configured speed (MT/s, SMBIOS >= 2.7)
+    pub configured_speed: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub serial_number: Option<String>,
+    pub part_number: Option<String>,
+}
+
+impl From<&SmbiosStructure> for SmbiosMemoryDevice {
+    fn from(s: &SmbiosStructure) -> Self {
+        let size = match s.word(0x0c) {
+            None | Some(0) | Some(0xffff) => None,
+            // `Extended Size` in MiB (SMBIOS >= 2.7)
+            Some(0x7fff) => s
+                .dword(0x1c)
+                .map(|mib| Bytes::from_kib((mib & 0x7fff_ffff) as u64 * 1024)),
+            // bit 15 set, size in KiB, otherwise MiB
+            Some(n) if n & 0x8000 != 0 => Some(Bytes::from_kib((n & 0x7fff) as u64)),
+            Some(n) => Some(Bytes::from_kib(n as u64 * 1024)),
+        };
+
+        // 0xffff, `Extended Speed` in MT/s (SMBIOS >= 3.3)
+        let speed = |offset, extended_offset| match s.word(offset) {
+            None | Some(0) => None,
+            Some(0xffff) => s.dword(extended_offset).filter(|n| *n != 0),
+            Some(n) => Some(n as u32),
+        };
+
+        SmbiosMemoryDevice {
+            handle: s.handle,
+            array_handle: s.word(0x04).unwrap_or_default(),
+            size,
+            form_factor: s.byte(0x0e).unwrap_or_default(),
+            locator: s.string(0x10),
+            bank_locator: s.string(0x11),
+            memory_type: s.byte(0x12).unwrap_or_default(),
+            speed: speed(0x15, 0x54),
+            configured_speed: speed(0x20, 0x58),
+            manufacturer: s.string(0x17),
+            serial_number: s.string(0x18),
+            part_number: s.string(0x1a),
+        }
+    }
+}
+
+/// decode memory type (e.g. `0x1a` -> `DDR4`)
+pub fn memory_type_to_str(memory_type: u8) -> &'static str {
+    match memory_type {
+        0x01 => "Other",
+        0x03 => "DRAM",
+        0x04 => "EDRAM",
+        0x05 => "VRAM",
+        0x06 => "SRAM",
+        0x07 => "RAM",
+        0x08 => "ROM",
+        0x09 => "Flash",
+        0x0a => "EEPROM",
+        0x0b => "FEPROM",
+        0x0c => "EPROM",
+        0x0d => "CDRAM",
+        0x0e => "3DRAM",
+        0x0f => "SDRAM",
+        0x10 => "SGRAM",
+        0x11 => "RDRAM",
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x14 => "DDR2 FB-DIMM",
+        0x18 => "DDR3",
+        0x19 => "FBD2",
+        0x1a => "DDR4",
+        0x1b => "LPDDR",
+        0x1c => "LPDDR2",
+        0x1d => "LPDDR3",
+        0x1e => "LPDDR4",
+        0x1f => "NVDIMM",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => "Unknown",
+    }
+}
+
+/// decode memory form factor (e.g. `0x09` -> `DIMM`)
+pub fn memory_form_factor_to_str(form_factor: u8) -> &'static str {
+    match form_factor {
+        0x01 => "Other",
+        0x03 => "SIMM",
+        0x04 => "SIP",
+        0x05 => "Chip",
+        0x06 => "DIP",
+        0x07 => "ZIP",
+        0x08 => "Proprietary Card",
+        0x09 => "DIMM",
+        0x0a => "TSOP",
+        0x0b => "Row of chips",
+        0x0c => "RIMM",
+        0x0d => "SODIMM",
+        0x0e => "SRIMM",
+        0x0f => "FB-DIMM",
+        0x10 => "Die",
+        0x11 => "CAMM",
+        _ => "Unknown",
+    }
+}
+
+/// type 19, Memory Array Mapped Address
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SmbiosMemoryMapping {
+    /// handle of the `SmbiosMemoryArray` the range belongs to
+    pub array_handle: u16,
+    /// first byte of the range
+    pub start: Bytes,
+    /// last byte of the range
+    pub end: Bytes,
+}
+
+impl From<&SmbiosStructure> for SmbiosMemoryMapping {
+    fn from(s: &SmbiosStructure) -> Self {
+        let (start, end) = match (s.dword(0x04), s.dword(0x08)) {
+            // `Extended Starting/Ending Address` in bytes (SMBIOS >= 2.7)
+            (Some(0xffff_ffff), _) => (
+                Bytes(s.qword(0x0f).unwrap_or_default()),
+                Bytes(s.qword(0x17).unwrap_or_default()),
+            ),
+            // KiB, end address of the last KiB
+            (Some(start), Some(end)) => (
+                Bytes::from_kib(start as u64),
+                Bytes::from_kib(end as u64 + 1) - Bytes(1),
+            ),
+            _ => (Bytes(0), Bytes(0)),
+        };
+
+        SmbiosMemoryMapping {
+            array_handle: s.word(0x0c).unwrap_or_default(),
+            start,
+            end,
+        }
+    }
+}
+
+impl SmbiosMemoryMapping {
+    pub fn size(&self) -> Bytes {
+        match self.end.as_u64() >= self.start.as_u64() {
+            true => self.end - self.start + Bytes(1),
+            false => Bytes(0),
+        }
+    }
+}
+
+// typed records of the SMBIOS table
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Smbios {
+    /// (`major`, `minor`) from entry point (e.g. `(3, 3)`)
+    pub version: Option<(u8, u8)>,
+    pub bios: Option<SmbiosBios>,
+    pub system: Option<SmbiosSystem>,
+    pub baseboard: Option<SmbiosBaseboard>,
+    pub chassis: Option<SmbiosChassis>,
+    pub processors: Vec<SmbiosProcessor>,
+    pub memory_arrays: Vec<SmbiosMemoryArray>,
+    pub memory_devices: Vec<SmbiosMemoryDevice>,
+    pub memory_mappings: Vec<SmbiosMemoryMapping>,
+    /// tables exist, but are readable by root only
+    pub permission_denied: bool,
+}
+
+impl Smbios {
+    // NOTE: empty if there are no SMBIOS tables (e.g. ARM boards with device tree)
+    pub fn get() -> Result<Self, Error> {
+        Smbios::get_from_path(std::path::Path::new(SYS_FIRMWARE_DMI_TABLES))
+    }
+
+    /// tables of given directory (e.g. `/sys/firmware/dmi/tables`, a dump)
+    pub fn get_from_path(path: &std::path::Path) -> Result<Self, Error> {
+        let table = match std::fs::read(path.join(DMI_TABLE)) {
+            Ok(table) => table,
+            Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                return Ok(Smbios {
+                    permission_denied: true,
+                    ..Default::default()
+                })
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Smbios::default()),
+            Err(e) => return Err(e),
+        };
+        let entry_point = std::fs::read(path.join(DMI_ENTRY_POINT)).unwrap_or_default();
+
+        Ok(Smbios::parse(&entry_point, &table))
+    }
+
+    pub fn parse(entry_point: &[u8], table: &[u8]) -> Self {
+        let mut smbios = Smbios {
+            version: parse_smbios_version(entry_point),
+            ..Default::default()
+        };
+
+        for s in parse_smbios_structures(table).iter() {
+            match s.kind {
+                SMBIOS_BIOS => smbios.bios = Some(s.into()),
+                SMBIOS_SYSTEM => smbios.system = Some(s.into()),
+                SMBIOS_BASEBOARD => {
+                    // NOTE: first baseboard is the motherboard, others are add-on modules
+                    smbios.baseboard.get_or_insert_with(|| s.into());
+                }
+                SMBIOS_CHASSIS => {
+                    smbios.chassis.get_or_insert_with(|| s.into());
+                }
+                SMBIOS_PROCESSOR => smbios.processors.push(s.into()),
+                SMBIOS_MEMORY_ARRAY => smbios.memory_arrays.push(s.into()),
+                SMBIOS_MEMORY_DEVICE => smbios.memory_devices.push(s.into()),
+                SMBIOS_MEMORY_MAPPING => smbios.memory_mappings.push(s.into()),
+                _ => {}
+            }
+        }
+
+        smbios
+    }
+
+    /// memory devices of system memory arrays (e.g. without video memory, flash)
+    pub fn memory_modules(&self) -> Vec<&SmbiosMemoryDevice> {
+        let system_arrays: Vec<u16> = self
+            .memory_arrays
+            .iter()
+            .filter(|array| array.usage == MEMORY_ARRAY_USE_SYSTEM)
+            .map(|array| array.handle)
+            .collect();
+
+        self.memory_devices
+            .iter()
+            .filter(|device| {
+                system_arrays.is_empty() || system_arrays.contains(&device.array_handle)
+            })
+            .collect()
+    }
+
+    /// installed system memory, sum of module sizes
+    pub fn installed_memory(&self) -> Bytes {
+        self.memory_modules()
+            .iter()
+            .filter_map(|device| device.size)
+            .fold(Bytes(0), |sum, size| sum + size)
+    }
+
+    /// maximum system memory supported by the board, `None` if unknown
+    pub fn max_memory(&self) -> Option<Bytes> {
+        self.memory_arrays
+            .iter()
+            .filter(|array| array.usage == MEMORY_ARRAY_USE_SYSTEM)
+            .map(|array| array.max_capacity)
+            .try_fold(Bytes(0), |sum, capacity| Some(sum + capacity?))
+            .filter(|sum| sum.as_u64() > 0)
+    }
+
+    /// memory mapped into the address space by the firmware
+    pub fn mapped_memory(&self) -> Bytes {
+        self.memory_mappings
+            .iter()
+            .fold(Bytes(0), |sum, mapping| sum + mapping.size())
+    }
+}
+
+#[cfg(test)]
+fn smbios_test_dump(name: &str) -> Smbios {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata/smbios")
+        .join(name);
+    Smbios::get_from_path(&path).unwrap()
+}
+
+#[test]
+fn smbios_dump_desktop_test() {
+    // SMBIOS 3.0 entry point, 2 of 4 slots populated
+    let smbios = smbios_test_dump("dell-precision-3620");
+
+    assert_eq!(smbios.version, Some((3, 0)));
+    assert_eq!(
+        smbios.bios,
+        Some(SmbiosBios {
+            vendor: Some("Dell Inc.".to_string()),
+            version: Some("2.1.9".to_string()),
+            release_date: Some("01/24/2017".to_string()),
+        })
+    );
+
+    let system = smbios.system.clone().unwrap();
+    assert_eq!(system.manufacturer.as_deref(), Some("Dell Inc."));
+    assert_eq!(system.product_name.as_deref(), Some("Precision Tower 3620"));
+    assert_eq!(system.version, None);
+    assert_eq!(
+        system.uuid.as_deref(),
+        Some("4c4c4544-004b-4e10-805a-b7c04f424832")
+    );
+    assert_eq!(system.sku_number.as_deref(), Some("06B7"));
+    assert_eq!(system.family.as_deref(), Some("Precision"));
+
+    let baseboard = smbios.baseboard.clone().unwrap();
+    assert_eq!(baseboard.product_name.as_deref(), Some("09WH54"));
+    assert_eq!(baseboard.version.as_deref(), Some("A01"));
+    let chassis = smbios.chassis.clone().unwrap();
+    assert_eq!(chassis_type_to_str(chassis.chassis_type), "Desktop");
+
+    assert_eq!(
+        smbios.processors,
+        vec![SmbiosProcessor {
+            socket: Some("U3E1".to_string()),
+            manufacturer: Some("Intel(R) Corporation".to_string()),
+            version: Some("Intel(R) Xeon(R) CPU E3-1225 v5 @ 3.30GHz".to_string()),
+            max_speed: Some(4400),
+            current_speed: Some(3300),
+            core_count: Some(4),
+            thread_count: Some(4),
+        }]
+    );
+
+    assert_eq!(smbios.memory_arrays.len(), 1);
+    assert_eq!(smbios.memory_arrays[0].slots, 4);
+    assert_eq!(smbios.max_memory(), Some(Bytes::from_kib(64 * 1024 * 1024)));
+
+    let modules = smbios.memory_modules();
+    assert_eq!(modules.len(), 4);
+    assert_eq!(modules[0].locator.as_deref(), Some("DIMM1"));
+    assert_eq!(modules[0].size, Some(Bytes::from_kib(8 * 1024 * 1024)));
+    assert_eq!(memory_type_to_str(modules[0].memory_type), "DDR4");
+    assert_eq!(memory_form_factor_to_str(modules[0].form_factor), "DIMM");
+    assert_eq!(modules[0].speed, Some(2133));
+    assert_eq!(modules[0].configured_speed, Some(2133));
+    // JEDEC id instead of a name (SK Hynix)
+    assert_eq!(modules[0].manufacturer.as_deref(), Some("80AD000080AD"));
+    // trailing padding trimmed
+    assert_eq!(modules[0].part_number.as_deref(), Some("HMA41GU6AFR8N-TF"));
+    assert_eq!(modules[1].serial_number.as_deref(), Some("28221928"));
+    // empty slots
+    assert_eq!(modules[2].locator.as_deref(), Some("DIMM3"));
+    assert_eq!(modules[2].size, None);
+    assert_eq!(modules[3].size, None);
+    assert_eq!(modules[3].manufacturer, None);
+    assert_eq!(smbios.installed_memory(), Bytes::from_kib(16 * 1024 * 1024));
+
+    assert_eq!(smbios.memory_mappings.len(), 1);
+    assert_eq!(smbios.mapped_memory(), Bytes::from_kib(16 * 1024 * 1024));
+}
+
+#[test]
+fn smbios_dump_notebook_test() {
+    // SMBIOS 2.7 entry point (`_SM_`), both slots populated
+    let smbios = smbios_test_dump("lenovo-thinkpad-t430");
+
+    assert_eq!(smbios.version, Some((2, 7)));
+    let bios = smbios.bios.clone().unwrap();
+    assert_eq!(bios.vendor.as_deref(), Some("LENOVO"));
+    assert_eq!(bios.version.as_deref(), Some("G1ET45WW (1.20 )"));
+
+    // model name in version (Lenovo)
+    let system = smbios.system.clone().unwrap();
+    assert_eq!(system.product_name.as_deref(), Some("2347A49"));
+    assert_eq!(system.version.as_deref(), Some("ThinkPad T430"));
+    assert_eq!(system.family.as_deref(), Some("ThinkPad T430"));
+    assert_eq!(
+        system.uuid.as_deref(),
+        Some("b4c3d881-5192-11cb-aa19-e6932635bc8b")
+    );
+    let chassis = smbios.chassis.clone().unwrap();
+    assert_eq!(chassis_type_to_str(chassis.chassis_type), "Notebook");
+
+    assert_eq!(smbios.processors.len(), 1);
+    assert_eq!(
+        smbios.processors[0].version.as_deref(),
+        Some("Intel(R) Core(TM) i5-3320M CPU @ 2.60GHz")
+    );
+    assert_eq!(smbios.processors[0].core_count, Some(2));
+    assert_eq!(smbios.processors[0].thread_count, Some(4));
+
+    assert_eq!(smbios.max_memory(), Some(Bytes::from_kib(16 * 1024 * 1024)));
+    let modules = smbios.memory_modules();
+    assert_eq!(modules.len(), 2);
+    for module in modules.iter() {
+        assert_eq!(module.size, Some(Bytes::from_kib(4 * 1024 * 1024)));
+        assert_eq!(memory_type_to_str(module.memory_type), "DDR3");
+        assert_eq!(memory_form_factor_to_str(module.form_factor), "SODIMM");
+        assert_eq!(module.speed, Some(1600));
+        assert_eq!(module.manufacturer.as_deref(), Some("Hynix/Hyundai"));
+        assert_eq!(module.part_number.as_deref(), Some("HMT351S6CFR8C-PB"));
+    }
+    assert_eq!(modules[0].locator.as_deref(), Some("ChannelA-DIMM0"));
+    assert_eq!(modules[1].bank_locator.as_deref(), Some("BANK 2"));
+    assert_eq!(smbios.installed_memory(), Bytes::from_kib(8 * 1024 * 1024));
+    assert_eq!(smbios.mapped_memory(), Bytes::from_kib(8 * 1024 * 1024));
+}
+
+#[test]
+fn smbios_memory_device_size_test() {
+    let device = |size: u16, extended: u32| {
+        let mut data = vec![SMBIOS_MEMORY_DEVICE, 0x22, 0x00, 0x00];
+        data.resize(0x22, 0);
+        data[0x0c..0x0e].copy_from_slice(&size.to_le_bytes());
+        data[0x1c..0x20].copy_from_slice(&extended.to_le_bytes());
+        SmbiosMemoryDevice::from(&SmbiosStructure {
+            kind: SMBIOS_MEMORY_DEVICE,
+            data,
+            ..Default::default()
+        })
+    };
+
+    // MiB
+    assert_eq!(
+        device(0x4000, 0).size,
+        Some(Bytes::from_kib(16 * 1024 * 1024))
+    );
+    // KiB granularity
+    assert_eq!(device(0x8200, 0).size, Some(Bytes::from_kib(512)));
+    // extended size, 64 GiB
+    assert_eq!(
+        device(0x7fff, 0x10000).size,
+        Some(Bytes::from_kib(64 * 1024 * 1024))
+    );
+    // empty slot, unknown
+    assert_eq!(device(0x0000, 0).size, None);
+    assert_eq!(device(0xffff, 0).size, None);
+}
+
+#[test]
+fn smbios_malformed_test() {
+    // truncated structure, no string set terminator
+    let table = [SMBIOS_BIOS, 0x18, 0x00, 0x00, 0x01, 0x02];
+    assert!(parse_smbios_structures(&table).is_empty());
+    // length below header size
+    let table = [SMBIOS_BIOS, 0x02, 0x00, 0x00, 0x00, 0x00];
+    assert!(parse_smbios_structures(&table).is_empty());
+    assert_eq!(parse_smbios_version(b"_DMI_"), None);
+    // `_SM3_` / `_SM_` anchors with major and minor version
+    let mut entry_point = b"_SM3_\x00\x18\x03\x03".to_vec();
+    assert_eq!(parse_smbios_version(&entry_point), Some((3, 3)));
+    entry_point.truncate(7);
+    assert_eq!(parse_smbios_version(&entry_point), None);
+}
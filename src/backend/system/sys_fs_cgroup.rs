@@ -0,0 +1,506 @@
+/// get info from sysfs
+/// ```text
+/// /sys/fs/cgroup/<CGROUP>/cpu.stat
+/// /sys/fs/cgroup/<CGROUP>/memory.current
+/// /sys/fs/cgroup/<CGROUP>/memory.max
+/// /sys/fs/cgroup/<CGROUP>/memory.stat
+/// /sys/fs/cgroup/<CGROUP>/io.stat
+/// /sys/fs/cgroup/<CGROUP>/pids.current
+/// /sys/fs/cgroup/<CGROUP>/*.pressure
+/// /sys/fs/cgroup/<CGROUP>/cpu.max
+/// /sys/fs/cgroup/<CGROUP>/cpuset.cpus.effective
+/// /sys/fs/cgroup/<CGROUP>/memory.swap.current
+/// /sys/fs/cgroup/<CGROUP>/memory.swap.max
+/// /proc/self/cgroup
+/// ```
+///
+/// cgroup v2 (unified hierarchy) only, files of disabled controllers do not exist
+/// NOTE: the root cgroup has no `memory.*` and `pids.*` files
+/// INFO: [kernel.org](https://docs.kernel.org/admin-guide/cgroup-v2.html)
+use std::collections::BTreeMap;
+use std::fs::read_dir;
+use std::io::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{get_string_from_file, get_string_from_path, parse_cpu_list, Bytes, Pressures};
+
+pub const SYS_FS_CGROUP: &str = "/sys/fs/cgroup";
+/// `/sys/fs/cgroup/cgroup.controllers`, exists in cgroup v2 only
+pub(crate) const CGROUP_CONTROLLERS: &str = "cgroup.controllers";
+/// `/sys/fs/cgroup/<CGROUP>/cpu.stat`
+pub(crate) const CGROUP_CPU_STAT: &str = "cpu.stat";
+/// `/sys/fs/cgroup/<CGROUP>/memory.current`
+pub(crate) const CGROUP_MEMORY_CURRENT: &str = "memory.current";
+/// `/sys/fs/cgroup/<CGROUP>/memory.max` (e.g. `max`, `1073741824`)
+pub(crate) const CGROUP_MEMORY_MAX: &str = "memory.max";
+/// `/sys/fs/cgroup/<CGROUP>/memory.stat`
+pub(crate) const CGROUP_MEMORY_STAT: &str = "memory.stat";
+/// `/sys/fs/cgroup/<CGROUP>/io.stat`
+pub(crate) const CGROUP_IO_STAT: &str = "io.stat";
+/// `/sys/fs/cgroup/<CGROUP>/pids.current`
+pub(crate) const CGROUP_PIDS_CURRENT: &str = "pids.current";
+/// `/sys/fs/cgroup/<CGROUP>/cpu.max` (e.g. `max 100000`, `200000 100000`)
+pub(crate) const CGROUP_CPU_MAX: &str = "cpu.max";
+/// `/sys/fs/cgroup/<CGROUP>/cpuset.cpus.effective` (e.g. `0-3`)
+pub(crate) const CGROUP_CPUSET_CPUS_EFFECTIVE: &str = "cpuset.cpus.effective";
+/// `/sys/fs/cgroup/<CGROUP>/memory.swap.current`
+pub(crate) const CGROUP_MEMORY_SWAP_CURRENT: &str = "memory.swap.current";
+/// `/sys/fs/cgroup/<CGROUP>/memory.swap.max`
+pub(crate) const CGROUP_MEMORY_SWAP_MAX: &str = "memory.swap.max";
+/// `/proc/self/cgroup` (e.g. `0::/user.slice/user-1000.slice/session-2.scope`)
+pub const PROC_SELF_CGROUP: &str = "/proc/self/cgroup";
+
+/// `cpu.stat`, usage of the cgroup and all descendants
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CgroupCpuStat {
+    /// `usage_usec`
+    pub usage_usec: u64,
+    /// `user_usec`
+    pub user_usec: u64,
+    /// `system_usec`
+    pub system_usec: u64,
+    /// `nr_periods` enforcement periods (`cpu.max` set)
+    pub nr_periods: u64,
+    /// `nr_throttled` periods with throttled tasks
+    pub nr_throttled: u64,
+    /// `throttled_usec`
+    pub throttled_usec: u64,
+}
+
+impl FromStr for CgroupCpuStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stat = CgroupCpuStat::default();
+
+        for (key, value) in s.lines().filter_map(|line| line.split_once(' ')) {
+            let value = value.trim().parse().unwrap_or_default();
+            match key {
+                "usage_usec" => stat.usage_usec = value,
+                "user_usec" => stat.user_usec = value,
+                "system_usec" => stat.system_usec = value,
+                "nr_periods" => stat.nr_periods = value,
+                "nr_throttled" => stat.nr_throttled = value,
+                "throttled_usec" => stat.throttled_usec = value,
+                _ => {}
+            }
+        }
+
+        Ok(stat)
+    }
+}
+
+/// `memory.stat`, selected counters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CgroupMemoryStat {
+    /// `anon` anonymous memory (e.g. heap, stack)
+    pub anon: Bytes,
+    /// `file` page cache
+    pub file: Bytes,
+    /// `kernel` kernel memory (e.g. slab, page tables)
+    pub kernel: Bytes,
+    /// `shmem` shared memory and tmpfs
+    pub shmem: Bytes,
+    /// `sock` network buffers
+    pub sock: Bytes,
+}
+
+impl FromStr for CgroupMemoryStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stat = CgroupMemoryStat::default();
+
+        for (key, value) in s.lines().filter_map(|line| line.split_once(' ')) {
+            let value = Bytes(value.trim().parse().unwrap_or_default());
+            match key {
+                "anon" => stat.anon = value,
+                "file" => stat.file = value,
+                "kernel" => stat.kernel = value,
+                "shmem" => stat.shmem = value,
+                "sock" => stat.sock = value,
+                _ => {}
+            }
+        }
+
+        Ok(stat)
+    }
+}
+
+/// `io.stat`, sum of all devices
+/// (e.g. `8:0 rbytes=90430464 wbytes=299008 rios=8950 wios=10 dbytes=0 dios=0`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CgroupIoStat {
+    /// `rbytes`
+    pub rbytes: Bytes,
+    /// `wbytes`
+    pub wbytes: Bytes,
+    /// `rios` read operations
+    pub rios: u64,
+    /// `wios` write operations
+    pub wios: u64,
+}
+
+impl FromStr for CgroupIoStat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut stat = CgroupIoStat::default();
+
+        for (key, value) in s.split_whitespace().filter_map(|kv| kv.split_once('=')) {
+            let value: u64 = value.parse().unwrap_or_default();
+            match key {
+                "rbytes" => stat.rbytes += Bytes(value),
+                "wbytes" => stat.wbytes += Bytes(value),
+                "rios" => stat.rios += value,
+                "wios" => stat.wios += value,
+                _ => {}
+            }
+        }
+
+        Ok(stat)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cgroup {
+    /// path relative to `/sys/fs/cgroup` (e.g. `system.slice/ssh.service`), empty for root
+    pub path: String,
+    pub cpu: Option<CgroupCpuStat>,
+    pub memory_current: Option<Bytes>,
+    /// `None` if unlimited (`max`) or no memory controller
+    pub memory_max: Option<Bytes>,
+    pub memory_stat: Option<CgroupMemoryStat>,
+    pub io: Option<CgroupIoStat>,
+    pub pids_current: Option<u64>,
+    pub pressures: Pressures,
+    /// `BTreeMap<name, Cgroup{}>` child cgroups (e.g. `system.slice`)
+    pub children: BTreeMap<String, Cgroup>,
+}
+
+impl Cgroup {
+    /// get cgroup and all descendants by given path (e.g. `/sys/fs/cgroup/system.slice`)
+    pub fn get<P: AsRef<Path>>(root: P, path: &str) -> Result<Self, Error> {
+        let dir = root.as_ref().join(path);
+
+        let mut cgroup = Cgroup {
+            path: path.to_string(),
+            cpu: get_string_from_path(&dir, CGROUP_CPU_STAT).and_then(|s| s.parse().ok()),
+            memory_current: get_string_from_path(&dir, CGROUP_MEMORY_CURRENT)
+                .and_then(|s| s.parse().ok())
+                .map(Bytes),
+            memory_max: get_string_from_path(&dir, CGROUP_MEMORY_MAX)
+                .and_then(|s| s.parse().ok())
+                .map(Bytes),
+            memory_stat: get_string_from_path(&dir, CGROUP_MEMORY_STAT)
+                .and_then(|s| s.parse().ok()),
+            io: get_string_from_path(&dir, CGROUP_IO_STAT).and_then(|s| s.parse().ok()),
+            pids_current: get_string_from_path(&dir, CGROUP_PIDS_CURRENT)
+                .and_then(|s| s.parse().ok()),
+            pressures: Pressures::get_cgroup(&dir)?,
+            children: BTreeMap::new(),
+        };
+
+        for entry in read_dir(&dir)?.flatten() {
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            let child_path = match path.is_empty() {
+                true => name.clone(),
+                false => format!("{}/{}", path, name),
+            };
+            // NOTE: cgroups can be removed while being read
+            if let Ok(child) = Cgroup::get(root.as_ref(), &child_path) {
+                cgroup.children.insert(name, child);
+            }
+        }
+
+        Ok(cgroup)
+    }
+
+    /// name of the cgroup (e.g. `ssh.service`), `/` for root
+    pub fn get_name(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((_, name)) => name,
+            None if self.path.is_empty() => "/",
+            None => &self.path,
+        }
+    }
+
+    /// number of all descendants
+    pub fn get_descendants(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| 1 + child.get_descendants())
+            .sum()
+    }
+
+    /// CPU usage between two snapshots as percent of one CPU
+    pub fn get_cpu_percent(&self, start: Option<&Cgroup>, dt: u64) -> f64 {
+        let end = self.cpu.map(|cpu| cpu.usage_usec).unwrap_or_default();
+        let start = start
+            .and_then(|start| start.cpu)
+            .map(|cpu| cpu.usage_usec)
+            .unwrap_or(end);
+
+        end.saturating_sub(start) as f64 / (dt as f64 * 1_000_000.0) * 100.0
+    }
+
+    /// read and write rates (B/s) between two snapshots
+    pub fn get_io_rate(&self, start: Option<&Cgroup>, dt: u64) -> (f64, f64) {
+        let end = self.io.unwrap_or_default();
+        let start = start.and_then(|start| start.io).unwrap_or(end);
+
+        (
+            (end.rbytes - start.rbytes).as_f64() / dt as f64,
+            (end.wbytes - start.wbytes).as_f64() / dt as f64,
+        )
+    }
+}
+
+/// cgroup v2 hierarchy
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cgroups {
+    /// `None` if cgroup v2 is not mounted at `/sys/fs/cgroup` (e.g. cgroup v1, hybrid)
+    pub root: Option<Cgroup>,
+}
+
+impl Cgroups {
+    pub fn get() -> Result<Self, Error> {
+        Cgroups::get_from_path(SYS_FS_CGROUP)
+    }
+
+    pub fn get_from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if !path.join(CGROUP_CONTROLLERS).exists() {
+            return Ok(Cgroups::default());
+        }
+
+        Ok(Cgroups {
+            root: Some(Cgroup::get(path, "")?),
+        })
+    }
+}
+
+/// parse `cpu.max` into CPUs (e.g. `200000 100000` -> `2.0`), `None` if unlimited (`max`)
+pub fn parse_cpu_max(s: &str) -> Option<f64> {
+    let mut values = s.split_whitespace();
+    let quota: f64 = values.next()?.parse().ok()?;
+    let period: f64 = values.next()?.parse().ok()?;
+
+    match period > 0.0 {
+        true => Some(quota / period),
+        false => None,
+    }
+}
+
+/// own cgroup path from `/proc/self/cgroup` (e.g. `/`, `/system.slice/docker-1234.scope`)
+pub fn parse_proc_self_cgroup(s: &str) -> Option<String> {
+    s.lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.trim().to_string())
+}
+
+/// effective limits of the own cgroup, the lowest limit of all ancestors
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CgroupLimits {
+    /// own cgroup path (e.g. `/` in a cgroup namespace)
+    pub path: String,
+    /// `/sys/fs/cgroup` is not the host root, limits are set on the visible root (e.g. container)
+    pub namespaced: bool,
+    /// `cpu.max` in CPUs (e.g. `1.5`), `None` if unlimited
+    pub cpu_max: Option<f64>,
+    /// `cpuset.cpus.effective`, empty if the cpuset controller is not enabled
+    pub cpus: Vec<usize>,
+    /// `cpu.stat` of the own cgroup
+    pub cpu: Option<CgroupCpuStat>,
+    pub memory_current: Option<Bytes>,
+    /// `None` if unlimited
+    pub memory_max: Option<Bytes>,
+    pub swap_current: Option<Bytes>,
+    /// `None` if unlimited
+    pub swap_max: Option<Bytes>,
+}
+
+impl CgroupLimits {
+    pub fn get() -> Result<Self, Error> {
+        let own = get_string_from_file(PROC_SELF_CGROUP)?;
+        CgroupLimits::get_from_path(
+            SYS_FS_CGROUP,
+            &parse_proc_self_cgroup(&own).unwrap_or_default(),
+        )
+    }
+
+    /// get limits of given cgroup (e.g. `/system.slice/docker-1234.scope`) below given root
+    pub fn get_from_path<P: AsRef<Path>>(root: P, path: &str) -> Result<Self, Error> {
+        let root = root.as_ref();
+        let dir = root.join(path.trim_start_matches('/'));
+        let bytes = |dir: &Path, file: &str| {
+            get_string_from_path(dir, file)
+                .and_then(|s| s.parse().ok())
+                .map(Bytes)
+        };
+
+        let mut limits = CgroupLimits {
+            path: path.to_string(),
+            // NOTE: the host root cgroup has no `cpu.max` and `memory.max`
+            namespaced: root.join(CGROUP_MEMORY_MAX).exists() || root.join(CGROUP_CPU_MAX).exists(),
+            cpus: parse_cpu_list(
+                &get_string_from_path(&dir, CGROUP_CPUSET_CPUS_EFFECTIVE).unwrap_or_default(),
+            ),
+            cpu: get_string_from_path(&dir, CGROUP_CPU_STAT).and_then(|s| s.parse().ok()),
+            memory_current: bytes(&dir, CGROUP_MEMORY_CURRENT),
+            swap_current: bytes(&dir, CGROUP_MEMORY_SWAP_CURRENT),
+            ..Default::default()
+        };
+
+        // own cgroup and all ancestors up to the visible root
+        for dir in dir.ancestors().take_while(|dir| dir.starts_with(root)) {
+            let lower = |limit: Option<f64>, value: Option<f64>| match (limit, value) {
+                (Some(limit), Some(value)) => Some(limit.min(value)),
+                (limit, value) => limit.or(value),
+            };
+            let cpu_max = get_string_from_path(dir, CGROUP_CPU_MAX).and_then(|s| parse_cpu_max(&s));
+            limits.cpu_max = lower(limits.cpu_max, cpu_max);
+            if let Some(max) = bytes(dir, CGROUP_MEMORY_MAX) {
+                limits.memory_max = Some(limits.memory_max.map_or(max, |limit| limit.min(max)));
+            }
+            if let Some(max) = bytes(dir, CGROUP_MEMORY_SWAP_MAX) {
+                limits.swap_max = Some(limits.swap_max.map_or(max, |limit| limit.min(max)));
+            }
+        }
+
+        Ok(limits)
+    }
+
+    /// usable CPUs, `cpu.max` or number of `cpuset` CPUs, whichever is lower
+    pub fn get_cpu_limit(&self) -> Option<f64> {
+        let cpuset = match self.cpus.is_empty() {
+            true => None,
+            false => Some(self.cpus.len() as f64),
+        };
+        match (self.cpu_max, cpuset) {
+            (Some(max), Some(cpuset)) => Some(max.min(cpuset)),
+            (max, cpuset) => max.or(cpuset),
+        }
+    }
+
+    /// CPU usage between two snapshots in CPUs (e.g. `1.5` = one and a half CPUs busy)
+    pub fn get_cpu_usage(&self, start: &CgroupLimits, dt: u64) -> f64 {
+        let end = self.cpu.map(|cpu| cpu.usage_usec).unwrap_or_default();
+        let start = start.cpu.map(|cpu| cpu.usage_usec).unwrap_or(end);
+        end.saturating_sub(start) as f64 / (dt as f64 * 1_000_000.0)
+    }
+
+    /// share of enforcement periods with throttled tasks between two snapshots (%)
+    pub fn get_throttled_percent(&self, start: &CgroupLimits) -> f64 {
+        let end = self.cpu.unwrap_or_default();
+        let start = start.cpu.unwrap_or(end);
+        let periods = end.nr_periods.saturating_sub(start.nr_periods);
+        match periods {
+            0 => 0.0,
+            periods => {
+                end.nr_throttled.saturating_sub(start.nr_throttled) as f64 / periods as f64 * 100.0
+            }
+        }
+    }
+}
+
+#[test]
+fn cgroup_stat_from_str_test() {
+    let s = "usage_usec 8372100
+user_usec 5103002
+system_usec 3269098
+nr_periods 10
+nr_throttled 2
+throttled_usec 41000
+";
+    let cpu: CgroupCpuStat = s.parse().unwrap();
+    assert_eq!(cpu.usage_usec, 8372100);
+    assert_eq!(cpu.nr_throttled, 2);
+    assert_eq!(cpu.throttled_usec, 41000);
+
+    let s = "anon 4096000\nfile 8192000\nkernel 1024\nkernel_stack 16384\nshmem 0\nsock 0\n";
+    let memory: CgroupMemoryStat = s.parse().unwrap();
+    assert_eq!(memory.anon, Bytes(4096000));
+    assert_eq!(memory.file, Bytes(8192000));
+    assert_eq!(memory.kernel, Bytes(1024));
+
+    let s = "8:0 rbytes=1000 wbytes=200 rios=10 wios=2 dbytes=0 dios=0
+259:0 rbytes=24 wbytes=0 rios=1 wios=0 dbytes=0 dios=0
+";
+    let io: CgroupIoStat = s.parse().unwrap();
+    assert_eq!(io.rbytes, Bytes(1024));
+    assert_eq!(io.wbytes, Bytes(200));
+    assert_eq!(io.rios, 11);
+}
+
+#[test]
+fn cgroups_get_from_path_test() {
+    let testdata = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/cgroup");
+
+    let root = testdata.join("host");
+    let cgroups = Cgroups::get_from_path(&root).unwrap();
+    let cgroup_root = cgroups.root.unwrap();
+    assert_eq!(cgroup_root.get_name(), "/");
+    assert_eq!(cgroup_root.get_descendants(), 2);
+
+    let service = &cgroup_root.children["system.slice"].children["ssh.service"];
+    assert_eq!(service.path, "system.slice/ssh.service");
+    assert_eq!(service.get_name(), "ssh.service");
+    assert_eq!(service.memory_current, Some(Bytes(4096)));
+    assert_eq!(service.memory_max, None);
+    assert_eq!(service.pids_current, Some(3));
+
+    let mut end = service.clone();
+    end.cpu = Some(CgroupCpuStat {
+        usage_usec: 2000000,
+        ..Default::default()
+    });
+    assert_eq!(end.get_cpu_percent(Some(service), 2), 50.0);
+
+    // no cgroup v2
+    assert_eq!(Cgroups::get_from_path(&testdata).unwrap().root, None);
+}
+
+#[test]
+fn cgroup_limits_get_from_path_test() {
+    assert_eq!(parse_cpu_max("200000 100000"), Some(2.0));
+    assert_eq!(parse_cpu_max("max 100000"), None);
+    assert_eq!(
+        parse_proc_self_cgroup("12:memory:/docker/1234\n0::/docker/1234\n"),
+        Some("/docker/1234".to_string())
+    );
+
+    // host view of a container cgroup, limits on the parent slice too
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/cgroup/docker");
+    let scope = root.join("system.slice/docker-1234.scope");
+
+    let start = CgroupLimits::get_from_path(&root, "/system.slice/docker-1234.scope").unwrap();
+    assert!(!start.namespaced);
+    assert_eq!(start.memory_max, Some(Bytes(1073741824)));
+    assert_eq!(start.memory_current, Some(Bytes(536870912)));
+    assert_eq!(start.swap_max, None);
+    assert_eq!(start.cpus, vec![0, 1, 2, 3]);
+    assert_eq!(start.get_cpu_limit(), Some(1.5));
+
+    let end = CgroupLimits {
+        cpu: Some(CgroupCpuStat {
+            usage_usec: 3000000,
+            nr_periods: 20,
+            nr_throttled: 6,
+            ..Default::default()
+        }),
+        ..start.clone()
+    };
+    assert_eq!(end.get_cpu_usage(&start, 2), 1.0);
+    assert_eq!(end.get_throttled_percent(&start), 50.0);
+
+    // cgroup namespace, own cgroup is the visible root
+    let limits = CgroupLimits::get_from_path(&scope, "/").unwrap();
+    assert!(limits.namespaced);
+    assert_eq!(limits.memory_max, None);
+    assert_eq!(limits.cpu_max, Some(1.5));
+}
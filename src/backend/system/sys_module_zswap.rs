@@ -0,0 +1,51 @@
+/// get info from sysfs
+/// ```text
+/// /sys/module/zswap/parameters/enabled
+///                             /compressor
+///                             /zpool
+///                             /max_pool_percent
+/// ```
+///
+/// NOTE: pool usage is read from `/proc/meminfo` `Zswap`, `Zswapped` (kernel >= 5.19),
+/// no debugfs required
+/// INFO: [kernel.org](https://docs.kernel.org/admin-guide/mm/zswap.html)
+use std::io::Error;
+use std::path::Path;
+
+use crate::get_string_from_path;
+
+pub const SYS_MODULE_ZSWAP_PARAMETERS: &str = "/sys/module/zswap/parameters";
+/// `/sys/module/zswap/parameters/enabled` (`Y` or `N`)
+pub(crate) const ZSWAP_ENABLED: &str = "enabled";
+/// `/sys/module/zswap/parameters/compressor`
+pub(crate) const ZSWAP_COMPRESSOR: &str = "compressor";
+/// `/sys/module/zswap/parameters/zpool` (removed in 6.15, zsmalloc only)
+pub(crate) const ZSWAP_ZPOOL: &str = "zpool";
+/// `/sys/module/zswap/parameters/max_pool_percent`
+pub(crate) const ZSWAP_MAX_POOL_PERCENT: &str = "max_pool_percent";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Zswap {
+    /// `enabled`, `None` if kernel is built without zswap
+    pub enabled: Option<bool>,
+    /// `compressor` (e.g. `lzo`, `zstd`)
+    pub compressor: Option<String>,
+    /// `zpool` (e.g. `zbud`, `z3fold`, `zsmalloc`)
+    pub zpool: Option<String>,
+    /// `max_pool_percent` maximum pool size as percent of RAM
+    pub max_pool_percent: Option<u64>,
+}
+
+impl Zswap {
+    pub fn get() -> Result<Self, Error> {
+        let path = Path::new(SYS_MODULE_ZSWAP_PARAMETERS);
+
+        Ok(Zswap {
+            enabled: get_string_from_path(path, ZSWAP_ENABLED).map(|s| s == "Y" || s == "1"),
+            compressor: get_string_from_path(path, ZSWAP_COMPRESSOR),
+            zpool: get_string_from_path(path, ZSWAP_ZPOOL),
+            max_pool_percent: get_string_from_path(path, ZSWAP_MAX_POOL_PERCENT)
+                .and_then(|s| s.parse().ok()),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, NaiveDate};
 use std::borrow::Cow;
 use std::ffi::CStr;
 use std::io::Error;
@@ -84,6 +85,86 @@ impl Uname {
             Err(Error::last_os_error())
         }
     }
+
+    /// kernel build date from `version`, see `parse_kernel_build_date()`
+    pub fn get_build_date(&self) -> Option<NaiveDate> {
+        parse_kernel_build_date(&self.version)
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// get build date from kernel version string, formats:
+/// ```text
+/// #1 SMP PREEMPT_DYNAMIC Thu Mar 14 09:12:42 UTC 2024
+/// #1 SMP PREEMPT_DYNAMIC Fri, 19 Jul 2024 12:34:56 +0000
+/// #1 SMP PREEMPT_DYNAMIC Debian 6.1.76-1 (2024-02-01)
+/// #1 SMP PREEMPT_DYNAMIC @1706745600 (reproducible builds, `SOURCE_DATE_EPOCH`)
+/// ```
+pub fn parse_kernel_build_date(version: &str) -> Option<NaiveDate> {
+    let tokens: Vec<&str> = version
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    // NOTE: `@0` is an unset epoch, not a date
+    if let Some(epoch) = tokens.iter().find_map(|t| t.strip_prefix('@')) {
+        return epoch
+            .parse::<i64>()
+            .ok()
+            .filter(|epoch| *epoch > 0)
+            .and_then(|epoch| DateTime::from_timestamp(epoch, 0))
+            .map(|date| date.date_naive());
+    }
+
+    if let Some(date) = tokens
+        .iter()
+        .find_map(|t| NaiveDate::parse_from_str(t, "%Y-%m-%d").ok())
+    {
+        return Some(date);
+    }
+
+    let i = tokens.iter().position(|t| MONTHS.contains(t))?;
+    let month = MONTHS.iter().position(|m| *m == tokens[i])? as u32 + 1;
+    // day after (`Mar 14`) or before (`19 Jul`) the month
+    let day = [
+        tokens.get(i + 1),
+        i.checked_sub(1).and_then(|i| tokens.get(i)),
+    ]
+    .iter()
+    .flatten()
+    .find_map(|t| t.parse::<u32>().ok().filter(|d| (1..=31).contains(d)))?;
+    let year = tokens[i..]
+        .iter()
+        .find_map(|t| t.parse::<i32>().ok().filter(|y| (1991..=9999).contains(y)))?;
+
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+#[test]
+fn parse_kernel_build_date_test() {
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d);
+
+    assert_eq!(
+        parse_kernel_build_date("#1 SMP PREEMPT_DYNAMIC Thu Mar 14 09:12:42 UTC 2024"),
+        date(2024, 3, 14)
+    );
+    assert_eq!(
+        parse_kernel_build_date("#1 SMP PREEMPT_DYNAMIC Fri, 19 Jul 2024 12:34:56 +0000"),
+        date(2024, 7, 19)
+    );
+    assert_eq!(
+        parse_kernel_build_date("#1 SMP PREEMPT_DYNAMIC Debian 6.1.76-1 (2024-02-01)"),
+        date(2024, 2, 1)
+    );
+    assert_eq!(
+        parse_kernel_build_date("#1 SMP PREEMPT_DYNAMIC @1706745600"),
+        date(2024, 2, 1)
+    );
+    assert_eq!(parse_kernel_build_date("#1 SMP PREEMPT_DYNAMIC @0"), None);
+    assert_eq!(parse_kernel_build_date("#1 SMP"), None);
 }
 
 #[test]
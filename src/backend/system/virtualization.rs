@@ -0,0 +1,309 @@
+/// detect virtual machine and container environment
+/// ```text
+/// /proc/cpuinfo                 `hypervisor` flag
+/// /sys/class/dmi/id/*           vendor strings (e.g. `QEMU`, `VMware, Inc.`)
+/// /sys/hypervisor/type          `xen`
+/// uname(2)                      WSL kernel release (e.g. `5.15.90.1-microsoft-standard-WSL2`)
+/// /.dockerenv                   Docker
+/// /run/.containerenv            Podman
+/// /run/systemd/container        set by container managers (e.g. `lxc`, `systemd-nspawn`)
+/// /proc/1/cgroup                cgroup path of init (e.g. `0::/docker/<ID>`)
+/// ```
+///
+/// NOTE: `/proc/1/environ` is not used, it is readable by root only
+use std::io::Error;
+use std::path::Path;
+
+use crate::{get_cpuinfo, get_dmi_info, get_string_from_file, DmiInfo, Uname};
+
+/// `/sys/hypervisor/type` (e.g. `xen`)
+pub const SYS_HYPERVISOR_TYPE: &str = "/sys/hypervisor/type";
+/// `/.dockerenv`
+pub const DOCKERENV: &str = "/.dockerenv";
+/// `/run/.containerenv`
+pub const RUN_CONTAINERENV: &str = "/run/.containerenv";
+/// `/run/systemd/container`
+pub const RUN_SYSTEMD_CONTAINER: &str = "/run/systemd/container";
+/// `/proc/1/cgroup`
+pub const PROC_1_CGROUP: &str = "/proc/1/cgroup";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hypervisor {
+    Kvm,
+    Qemu,
+    VMware,
+    VirtualBox,
+    HyperV,
+    Xen,
+    AmazonEc2,
+    GoogleCompute,
+    Parallels,
+    Bhyve,
+    Wsl1,
+    Wsl2,
+    /// `hypervisor` flag only, vendor not known
+    Unknown,
+}
+
+impl Hypervisor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Hypervisor::Kvm => "KVM",
+            Hypervisor::Qemu => "QEMU",
+            Hypervisor::VMware => "VMware",
+            Hypervisor::VirtualBox => "VirtualBox",
+            Hypervisor::HyperV => "Hyper-V",
+            Hypervisor::Xen => "Xen",
+            Hypervisor::AmazonEc2 => "Amazon EC2",
+            Hypervisor::GoogleCompute => "Google Compute Engine",
+            Hypervisor::Parallels => "Parallels",
+            Hypervisor::Bhyve => "bhyve",
+            Hypervisor::Wsl1 => "WSL1",
+            Hypervisor::Wsl2 => "WSL2",
+            Hypervisor::Unknown => "VM",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Container {
+    Docker,
+    Podman,
+    Lxc,
+    Kubernetes,
+    SystemdNspawn,
+    /// value of `/run/systemd/container` (e.g. `wsl`, `oci`)
+    Other(String),
+}
+
+impl Container {
+    pub fn name(&self) -> &str {
+        match self {
+            Container::Docker => "Docker",
+            Container::Podman => "Podman",
+            Container::Lxc => "LXC",
+            Container::Kubernetes => "Kubernetes",
+            Container::SystemdNspawn => "systemd-nspawn",
+            Container::Other(name) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Virtualization {
+    /// `None` on bare metal
+    pub hypervisor: Option<Hypervisor>,
+    /// `None` if not in a container
+    pub container: Option<Container>,
+}
+
+impl Virtualization {
+    pub fn get() -> Result<Self, Error> {
+        let uname = Uname::get()?;
+        let hypervisor_flag = get_cpuinfo()?.is_hypervisor();
+        let dmi = get_dmi_info()?;
+        let sys_hypervisor_type = get_string_from_file(SYS_HYPERVISOR_TYPE).ok();
+
+        let systemd_container = get_string_from_file(RUN_SYSTEMD_CONTAINER).ok();
+        let cgroup = get_string_from_file(PROC_1_CGROUP).unwrap_or_default();
+
+        Ok(Virtualization {
+            hypervisor: detect_hypervisor(
+                &uname.release,
+                hypervisor_flag,
+                &dmi,
+                sys_hypervisor_type.as_deref(),
+            ),
+            container: detect_container(
+                Path::new(DOCKERENV).exists(),
+                Path::new(RUN_CONTAINERENV).exists(),
+                systemd_container.as_deref(),
+                &cgroup,
+            ),
+        })
+    }
+}
+
+/// detect hypervisor, WSL kernel first, then Xen, DMI vendor strings and `hypervisor` flag
+pub fn detect_hypervisor(
+    kernel_release: &str,
+    hypervisor_flag: bool,
+    dmi: &DmiInfo,
+    sys_hypervisor_type: Option<&str>,
+) -> Option<Hypervisor> {
+    // e.g. `5.15.90.1-microsoft-standard-WSL2`, `4.4.0-19041-Microsoft`
+    let release = kernel_release.to_lowercase();
+    if release.contains("microsoft") {
+        return match release.contains("wsl2") || release.contains("microsoft-standard") {
+            true => Some(Hypervisor::Wsl2),
+            false => Some(Hypervisor::Wsl1),
+        };
+    }
+
+    if sys_hypervisor_type == Some("xen") {
+        return Some(Hypervisor::Xen);
+    }
+
+    let vendors = [
+        &dmi.sys_vendor,
+        &dmi.product_name,
+        &dmi.board_vendor,
+        &dmi.bios_vendor,
+    ];
+    for vendor in vendors.iter().filter_map(|s| s.as_deref()) {
+        let hypervisor = match vendor {
+            // QEMU with KVM acceleration sets `hypervisor` flag, TCG emulation usually not
+            "QEMU" if hypervisor_flag => Hypervisor::Kvm,
+            "QEMU" => Hypervisor::Qemu,
+            "KVM" => Hypervisor::Kvm,
+            "VMware, Inc." | "VMware" => Hypervisor::VMware,
+            "innotek GmbH" | "Oracle Corporation" | "VirtualBox" => Hypervisor::VirtualBox,
+            "Xen" => Hypervisor::Xen,
+            "Amazon EC2" => Hypervisor::AmazonEc2,
+            "Google" | "Google Compute Engine" => Hypervisor::GoogleCompute,
+            "Parallels Software International Inc." => Hypervisor::Parallels,
+            "BHYVE" => Hypervisor::Bhyve,
+            // NOTE: also used for Surface hardware, product name decides
+            "Microsoft Corporation" if dmi.product_name.as_deref() == Some("Virtual Machine") => {
+                Hypervisor::HyperV
+            }
+            _ => continue,
+        };
+        return Some(hypervisor);
+    }
+
+    match hypervisor_flag {
+        true => Some(Hypervisor::Unknown),
+        false => None,
+    }
+}
+
+/// detect container, marker files first, then cgroup path of init (`/proc/1/cgroup`)
+pub fn detect_container(
+    dockerenv: bool,
+    containerenv: bool,
+    systemd_container: Option<&str>,
+    cgroup: &str,
+) -> Option<Container> {
+    if dockerenv {
+        return Some(Container::Docker);
+    }
+    if containerenv {
+        return Some(Container::Podman);
+    }
+
+    match systemd_container {
+        Some("docker") => return Some(Container::Docker),
+        Some("podman") => return Some(Container::Podman),
+        Some("lxc") | Some("lxc-libvirt") => return Some(Container::Lxc),
+        Some("systemd-nspawn") => return Some(Container::SystemdNspawn),
+        Some(other) if !other.is_empty() => return Some(Container::Other(other.to_string())),
+        _ => {}
+    }
+
+    // e.g. `0::/docker/<ID>`, `0::/lxc.payload.<NAME>`, `0::/kubepods/burstable/<POD>/<ID>`
+    for path in cgroup.lines().filter_map(|line| line.splitn(3, ':').nth(2)) {
+        if path.contains("kubepods") {
+            return Some(Container::Kubernetes);
+        } else if path.contains("/docker") {
+            return Some(Container::Docker);
+        } else if path.contains("libpod") {
+            return Some(Container::Podman);
+        } else if path.contains("/lxc") {
+            return Some(Container::Lxc);
+        }
+    }
+
+    None
+}
+
+#[test]
+fn detect_hypervisor_test() {
+    let dmi = DmiInfo {
+        sys_vendor: Some("QEMU".to_string()),
+        product_name: Some("Standard PC (Q35 + ICH9, 2009)".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        detect_hypervisor("6.1.0-18-amd64", true, &dmi, None),
+        Some(Hypervisor::Kvm)
+    );
+    assert_eq!(
+        detect_hypervisor("6.1.0-18-amd64", false, &dmi, None),
+        Some(Hypervisor::Qemu)
+    );
+
+    let dmi = DmiInfo {
+        sys_vendor: Some("Microsoft Corporation".to_string()),
+        product_name: Some("Virtual Machine".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        detect_hypervisor("6.1.0-18-amd64", true, &dmi, None),
+        Some(Hypervisor::HyperV)
+    );
+    assert_eq!(
+        detect_hypervisor("5.15.90.1-microsoft-standard-WSL2", true, &dmi, None),
+        Some(Hypervisor::Wsl2)
+    );
+    assert_eq!(
+        detect_hypervisor("4.4.0-19041-Microsoft", false, &DmiInfo::default(), None),
+        Some(Hypervisor::Wsl1)
+    );
+
+    // Surface laptop, bare metal
+    let dmi = DmiInfo {
+        sys_vendor: Some("Microsoft Corporation".to_string()),
+        product_name: Some("Surface Laptop 4".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(detect_hypervisor("6.1.0-18-amd64", false, &dmi, None), None);
+
+    let dmi = DmiInfo::default();
+    assert_eq!(
+        detect_hypervisor("6.1.0", true, &dmi, Some("xen")),
+        Some(Hypervisor::Xen)
+    );
+    assert_eq!(
+        detect_hypervisor("6.1.0", true, &dmi, None),
+        Some(Hypervisor::Unknown)
+    );
+}
+
+#[test]
+fn detect_container_test() {
+    assert_eq!(
+        detect_container(true, false, None, ""),
+        Some(Container::Docker)
+    );
+    assert_eq!(
+        detect_container(false, true, None, ""),
+        Some(Container::Podman)
+    );
+    assert_eq!(
+        detect_container(false, false, Some("lxc"), ""),
+        Some(Container::Lxc)
+    );
+    assert_eq!(
+        detect_container(false, false, None, "0::/lxc.payload.web\n"),
+        Some(Container::Lxc)
+    );
+    assert_eq!(
+        detect_container(
+            false,
+            false,
+            None,
+            "12:memory:/docker/0123456789ab\n0::/docker/0123456789ab\n"
+        ),
+        Some(Container::Docker)
+    );
+    assert_eq!(
+        detect_container(false, false, None, "0::/kubepods/burstable/pod1/0123\n"),
+        Some(Container::Kubernetes)
+    );
+    // host, systemd unit cgroup
+    assert_eq!(
+        detect_container(false, false, None, "0::/init.scope\n"),
+        None
+    );
+}
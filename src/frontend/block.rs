@@ -70,12 +70,11 @@ pub fn block_device_temperature_to_string(device: &SysBlockInfo) -> String {
 /// get used, total, percent (used/total*100) hdd size
 pub fn get_block_device_stats(mount: &MountInfo) -> (u64, u64, u64) {
     let stat = Statvfs::get(mount.mnt_dir.as_str()).unwrap();
-    let available = stat.f_bsize * stat.f_bavail;
-    let total = stat.f_bsize * stat.f_blocks;
-    let used = total - available;
-    let percent = percent(used as f64, total as f64) as u64;
+    let total = stat.total();
+    let used = total - stat.available();
+    let percent = percent(used.as_f64(), total.as_f64()) as u64;
 
-    (used, total, percent)
+    (used.as_u64(), total.as_u64(), percent)
 }
 
 pub fn print_block_device(
@@ -122,8 +121,8 @@ pub fn print_block_device(
     let stats0 = block_snapshot0.stats.unwrap();
     let stats1 = block_snapshot1.stats.unwrap();
 
-    let r = ((stats1.read_sectors - stats0.read_sectors) * 512) / dt;
-    let w = ((stats1.write_sectors - stats0.write_sectors) * 512) / dt;
+    let r = (stats1.read_bytes() - stats0.read_bytes()).as_u64() / dt;
+    let w = (stats1.write_bytes() - stats0.write_bytes()).as_u64() / dt;
 
     let padding = format!("{}{}", padding, ' ');
     let icon = format!("{}{} ", padding, icon);
@@ -156,7 +155,7 @@ pub fn print_block_device(
                 // limit_string(&title, 15),
                 icon_name,
                 limit_string(&oem_model, 25),
-                human_byte_string(block_snapshot1.size as f64),
+                human_byte_string(block_snapshot1.size.as_f64()),
                 human_byte_string(r as f64),
                 human_byte_string(w as f64),
                 temp,
@@ -0,0 +1,317 @@
+use super::{
+    cpu_list_to_string, human_byte_string, human_byteps_string, limit_string, percent,
+    progress_bar, threshold_color,
+};
+use crate::{
+    odd_even, Bytes, Cgroup, CgroupLimits, Cgroups, CpuStats, MemInfo, Pressure, COLOR_DEFAULT,
+    ICON_DOCKER, ICON_RAM, ICON_SWAP,
+};
+use std::io::Error;
+
+/// cgroup levels shown, deeper cgroups are collapsed into their parent (e.g. `[+12]`)
+pub const CGROUP_DEPTH: usize = 2;
+
+// CPU usage (% of one CPU) colored as warning / critical
+const CGROUP_CPU_WARNING: f64 = 50.0;
+const CGROUP_CPU_CRITICAL: f64 = 90.0;
+// usage of a limit (%) colored as warning / critical
+const CGROUP_LIMIT_WARNING: f64 = 80.0;
+const CGROUP_LIMIT_CRITICAL: f64 = 95.0;
+// avg10 stall share (%) colored as warning / critical
+const CGROUP_PRESSURE_WARNING: f64 = 10.0;
+const CGROUP_PRESSURE_CRITICAL: f64 = 40.0;
+
+/// `some` avg10 of given pressure (e.g. `  4.5`), `-` if not available
+fn cgroup_pressure_to_string(pressure: Option<Pressure>) -> String {
+    match pressure.and_then(|pressure| pressure.some) {
+        Some(line) => format!(
+            "{}{:>5.1}{}",
+            threshold_color(
+                line.avg10,
+                CGROUP_PRESSURE_WARNING,
+                CGROUP_PRESSURE_CRITICAL
+            ),
+            line.avg10,
+            COLOR_DEFAULT
+        ),
+        None => format!("{:>5}", "-"),
+    }
+}
+
+pub fn print_cgroup(
+    padding: &str,
+    i: &mut usize,
+    depth: usize,
+    cgroup_snapshot1: &Cgroup,
+    cgroup_snapshot0: Option<&Cgroup>,
+    dt: u64,
+) -> String {
+    let mut s = String::new();
+
+    let cpu = cgroup_snapshot1.get_cpu_percent(cgroup_snapshot0, dt);
+    let (r, w) = cgroup_snapshot1.get_io_rate(cgroup_snapshot0, dt);
+
+    // collapsed subtree (e.g. `docker-1234.scope [+3]`)
+    let collapsed = depth >= CGROUP_DEPTH && !cgroup_snapshot1.children.is_empty();
+    let name = match collapsed {
+        true => format!(
+            "{} [+{}]",
+            cgroup_snapshot1.get_name(),
+            cgroup_snapshot1.get_descendants()
+        ),
+        false => cgroup_snapshot1.get_name().to_string(),
+    };
+    let name = match padding.is_empty() {
+        true => name,
+        false => format!("{}└ {}", padding, name),
+    };
+
+    let memory = match cgroup_snapshot1.memory_current {
+        Some(current) => human_byte_string(current.as_f64()),
+        None => format!("{:>11}", "-"),
+    };
+    let memory_max = match cgroup_snapshot1.memory_max {
+        Some(max) => human_byte_string(max.as_f64()),
+        None => format!("{:>11}", "-"),
+    };
+    let anon = match cgroup_snapshot1.memory_stat {
+        Some(stat) => human_byte_string(stat.anon.as_f64()),
+        None => format!("{:>11}", "-"),
+    };
+
+    s += &format!(
+        "{}   {:<34} {}{:>5.1} %{} {} / {} {} {} {} {:>6} {} {} {}\x1b[0m\n",
+        odd_even(*i),
+        limit_string(&name, 34),
+        threshold_color(cpu, CGROUP_CPU_WARNING, CGROUP_CPU_CRITICAL),
+        cpu,
+        COLOR_DEFAULT,
+        memory,
+        memory_max,
+        anon,
+        human_byteps_string(r),
+        human_byteps_string(w),
+        cgroup_snapshot1
+            .pids_current
+            .map(|pids| pids.to_string())
+            .unwrap_or("-".to_string()),
+        cgroup_pressure_to_string(cgroup_snapshot1.pressures.cpu),
+        cgroup_pressure_to_string(cgroup_snapshot1.pressures.memory),
+        cgroup_pressure_to_string(cgroup_snapshot1.pressures.io),
+    );
+
+    if collapsed {
+        return s;
+    }
+
+    // children by CPU usage, then memory (descending)
+    let mut children: Vec<(&String, &Cgroup, f64)> = cgroup_snapshot1
+        .children
+        .iter()
+        .map(|(name, child)| {
+            let child_start = cgroup_snapshot0.and_then(|start| start.children.get(name));
+            (name, child, child.get_cpu_percent(child_start, dt))
+        })
+        .collect();
+    children.sort_by(|(_, a, a_cpu), (_, b, b_cpu)| {
+        b_cpu
+            .total_cmp(a_cpu)
+            .then(b.memory_current.cmp(&a.memory_current))
+    });
+
+    let padding = match padding.is_empty() {
+        true => " ".to_string(),
+        false => format!("{}  ", padding),
+    };
+    for (name, child, _) in children {
+        let child_start = cgroup_snapshot0.and_then(|start| start.children.get(name));
+        *i += 1;
+        s += &print_cgroup(&padding, i, depth + 1, child, child_start, dt);
+    }
+
+    s
+}
+
+pub fn sys_fs_cgroup_to_string(cgroup_snapshot0: &mut Cgroups, dt: u64) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let cgroup_snapshot1 = Cgroups::get()?;
+
+    let Some(root) = &cgroup_snapshot1.root else {
+        s += &format!("{}   cgroup v2 not available\x1b[0m\n", odd_even(0));
+        *cgroup_snapshot0 = cgroup_snapshot1;
+        return Ok(s);
+    };
+
+    s += &format!(
+        "{}   {:<34} {:>7} {:>11}   {:>11} {:>11} {:>12} {:>12} {:>6} {:>5} {:>5} {:>5}\x1b[0m\n",
+        odd_even(0),
+        "cgroup",
+        "CPU",
+        "Memory",
+        "Max",
+        "Anon",
+        "Read",
+        "Write",
+        "Pids",
+        "PSI c",
+        "m",
+        "io"
+    );
+
+    let mut i = 1;
+    s += &print_cgroup("", &mut i, 0, root, cgroup_snapshot0.root.as_ref(), dt);
+
+    *cgroup_snapshot0 = cgroup_snapshot1;
+
+    Ok(s)
+}
+
+/// usage against limit (e.g. `  1.2 GiB /   2.0 GiB [||||-----] ( 60 %)`), unlimited without bar
+fn cgroup_limit_to_string(used: Bytes, max: Option<Bytes>) -> String {
+    match max {
+        Some(max) => {
+            let used_percent = percent(used.as_f64(), max.as_f64());
+            format!(
+                "{} / {} {}{}{} ({:>3.0} %)",
+                human_byte_string(used.as_f64()),
+                human_byte_string(max.as_f64()),
+                threshold_color(used_percent, CGROUP_LIMIT_WARNING, CGROUP_LIMIT_CRITICAL),
+                progress_bar(used.as_u64(), max.as_u64(), 20),
+                COLOR_DEFAULT,
+                used_percent
+            )
+        }
+        None => format!("{} / {:>11}", human_byte_string(used.as_f64()), "unlimited"),
+    }
+}
+
+/// effective limits of the own cgroup in a container, empty on the host
+/// NOTE: `/proc/meminfo` and `/proc/stat` show the host inside containers (without lxcfs)
+pub fn cgroup_limits_to_string(
+    limits_snapshot0: &mut CgroupLimits,
+    container: bool,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let limits_snapshot1 = CgroupLimits::get()?;
+
+    if !container && !limits_snapshot1.namespaced {
+        *limits_snapshot0 = limits_snapshot1;
+        return Ok(s);
+    }
+
+    let title = match limits_snapshot1.namespaced {
+        true => format!("{} (cgroup namespace)", limits_snapshot1.path),
+        false => limits_snapshot1.path.clone(),
+    };
+    s += &format!(
+        "{} {}  cgroup   {:<47} CPUs: {}\x1b[0m\n",
+        odd_even(0),
+        ICON_DOCKER,
+        limit_string(&title, 47),
+        match limits_snapshot1.cpus.is_empty() {
+            true => "-".to_string(),
+            false => cpu_list_to_string(&limits_snapshot1.cpus),
+        }
+    );
+
+    // CPU usage in CPUs against `cpu.max` or cpuset
+    let usage = limits_snapshot1.get_cpu_usage(limits_snapshot0, dt);
+    let cpu = match limits_snapshot1.get_cpu_limit() {
+        Some(limit) => {
+            let used_percent = percent(usage, limit);
+            format!(
+                "{:>6.2} CPUs / {:>6.2} CPUs {}{}{} ({:>3.0} %)",
+                usage,
+                limit,
+                threshold_color(used_percent, CGROUP_LIMIT_WARNING, CGROUP_LIMIT_CRITICAL),
+                progress_bar((usage * 100.0) as u64, (limit * 100.0) as u64, 20),
+                COLOR_DEFAULT,
+                used_percent
+            )
+        }
+        None => format!("{:>6.2} CPUs / {:>11}", usage, "unlimited"),
+    };
+    s += &format!(
+        "{} {}  CPU      {:<36}{}  throttled: {:>5.1} %\x1b[0m\n",
+        odd_even(1),
+        ICON_DOCKER,
+        "",
+        cpu,
+        limits_snapshot1.get_throttled_percent(limits_snapshot0)
+    );
+
+    s += &format!(
+        "{} {}  RAM      {:<36}{}\x1b[0m\n",
+        odd_even(2),
+        ICON_RAM,
+        "",
+        cgroup_limit_to_string(
+            limits_snapshot1.memory_current.unwrap_or_default(),
+            limits_snapshot1.memory_max
+        )
+    );
+    // NOTE: swap and host rows are optional
+    let mut i = 3;
+    if limits_snapshot1.swap_current.is_some() {
+        s += &format!(
+            "{} {}  Swap     {:<36}{}\x1b[0m\n",
+            odd_even(i),
+            ICON_SWAP,
+            "",
+            cgroup_limit_to_string(
+                limits_snapshot1.swap_current.unwrap_or_default(),
+                limits_snapshot1.swap_max
+            )
+        );
+        i += 1;
+    }
+
+    // host view, `/proc` belongs to the host, if it shows more than the limits
+    let meminfo = MemInfo::get()?;
+    // NOTE: first line is the sum of all CPUs (`cpu`)
+    let host_cpus = CpuStats::get()?.len().saturating_sub(1);
+    let more_memory = limits_snapshot1
+        .memory_max
+        .is_some_and(|max| meminfo.mem_total > max);
+    let more_cpus = limits_snapshot1
+        .get_cpu_limit()
+        .is_some_and(|limit| host_cpus as f64 > limit);
+    if more_memory || more_cpus {
+        s += &format!(
+            "{} {}  Host     /proc shows the host: {} CPUs, {} RAM\x1b[0m\n",
+            odd_even(i),
+            ICON_DOCKER,
+            host_cpus,
+            human_byte_string(meminfo.mem_total.as_f64()).trim(),
+        );
+    }
+
+    *limits_snapshot0 = limits_snapshot1;
+
+    Ok(s)
+}
+
+#[test]
+fn sys_fs_cgroup_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut snapshot0 = Cgroups::get().unwrap();
+    thread::sleep(Duration::from_secs(dt));
+    let s = sys_fs_cgroup_to_string(&mut snapshot0, dt).unwrap();
+    print!("{}", s);
+}
+
+#[test]
+fn cgroup_limits_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut snapshot0 = CgroupLimits::get().unwrap();
+    thread::sleep(Duration::from_secs(dt));
+    let s = cgroup_limits_to_string(&mut snapshot0, true, dt).unwrap();
+    print!("{}", s);
+}
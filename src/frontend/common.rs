@@ -20,8 +20,11 @@ pub fn mhz_to_ghz(n: usize) -> f64 {
     (n as f64) / 1000.0
 }
 
-// num as percent from into float (a / b * 100 %)
+// num as percent from into float (a / b * 100 %), `0.0` if b is `0.0` (e.g. no swap)
 pub fn percent(a: f64, b: f64) -> f64 {
+    if b == 0.0 {
+        return 0.0;
+    }
     (a / b) * 100.0
 }
 
@@ -63,8 +66,8 @@ pub fn human_byteps(mut value: f64) -> (f64, String) {
 
 // human friendly byte string (e.g. Byte, Kilo, Mega, ...)
 pub fn human_byteps_string(value: f64) -> String {
-    let (value, unit) = human_bitps(value);
-    format!("{:>6.1} {:>4}", value, unit)
+    let (value, unit) = human_byteps(value);
+    format!("{:>6.1} {:>5}", value, unit)
 }
 // human friendly (e.g. Byte, Kilo, Mega, ...)
 pub fn human_bitps(mut value: f64) -> (f64, String) {
@@ -118,6 +121,21 @@ pub fn odd_even(i: usize) -> String {
     odd_even
 }
 
+pub(crate) const COLOR_DEFAULT: &str = "\x1b[39m";
+pub(crate) const COLOR_WARNING: &str = "\x1b[33m";
+pub(crate) const COLOR_CRITICAL: &str = "\x1b[31m";
+
+// foreground color by thresholds (e.g. yellow >= warning, red >= critical)
+pub fn threshold_color(value: f64, warning: f64, critical: f64) -> &'static str {
+    if value >= critical {
+        COLOR_CRITICAL
+    } else if value >= warning {
+        COLOR_WARNING
+    } else {
+        COLOR_DEFAULT
+    }
+}
+
 #[test]
 fn human_b_test() {
     let a = human_byte(1024.0 * 1024.0);
@@ -125,12 +143,15 @@ fn human_b_test() {
 }
 
 // limit string to size of given length (e.g. `abcd...xyz`)
+// NOTE: counts chars, not bytes (e.g. non ASCII command lines)
 pub fn limit_string(s: &str, length: usize) -> String {
-    let n = s.len();
+    let n = s.chars().count();
     let k = length / 2 - 1;
 
     if n >= length {
-        format!("{}..{}", &s[..k], &s[(n - k)..])
+        let head: String = s.chars().take(k).collect();
+        let tail: String = s.chars().skip(n - k).collect();
+        format!("{}..{}", head, tail)
     } else {
         s.to_string()
     }
@@ -140,6 +161,8 @@ pub fn limit_string(s: &str, length: usize) -> String {
 fn limit_string_test() {
     let r = limit_string("123456789|abcdef|123456789", 20);
     assert_eq!(r, "123456789..123456789");
+    let r = limit_string("  └ grün|abcdef|123456789", 20);
+    assert_eq!(r, "  └ grün|..123456789");
 }
 
 #[test]
@@ -156,6 +179,33 @@ fn human_b_convert_test() {
     dbg!(s);
 }
 
+// cpu numbers into kernel cpu list format (e.g. `[0, 1, 2, 3, 8]` -> `0-3,8`)
+pub fn cpu_list_to_string(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+    for cpu in cpus.iter() {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *cpu => *end = *cpu,
+            _ => ranges.push((*cpu, *cpu)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => format!("{}", start),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+#[test]
+fn cpu_list_to_string_test() {
+    assert_eq!(cpu_list_to_string(&[0, 1, 2, 3, 8, 10, 11]), "0-3,8,10-11");
+    assert_eq!(cpu_list_to_string(&[]), "");
+}
+
 // XXX: ? `[====>.....] 20 - 21 char len`
 // print progress bar string
 // x - current value
@@ -165,7 +215,8 @@ pub fn progress_bar(x: u64, total: u64, length: u64) -> String {
     let mut s = String::new();
 
     // let n = (length as f64 * x / total).ceil() as u64;
-    let n = length * x / total;
+    // NOTE: empty bar if total is 0 (e.g. no swap)
+    let n = (length * x).checked_div(total).unwrap_or(0).min(length);
     for _ in 0..n {
         // # █
         s.push('#');
@@ -177,8 +228,39 @@ pub fn progress_bar(x: u64, total: u64, length: u64) -> String {
     format!("[{}]", s)
 }
 
+// print stacked progress bar string, one char per part (e.g. `[###++~~%%-------]`)
+// parts - (value, char), rest of total is filled with `-`
+pub fn stacked_progress_bar(parts: &[(u64, char)], total: u64, length: u64) -> String {
+    let mut s = String::new();
+
+    let mut n = 0;
+    for (x, c) in parts.iter() {
+        let k = (length * x).checked_div(total).unwrap_or(0).min(length - n);
+        for _ in 0..k {
+            s.push(*c);
+        }
+        n += k;
+    }
+    for _ in 0..(length - n) {
+        s.push('-');
+    }
+    format!("[{}]", s)
+}
+
+#[test]
+fn stacked_progress_bar_test() {
+    let s = stacked_progress_bar(&[(50, '#'), (25, '~')], 100, 12);
+    assert_eq!(s, "[######~~~---]");
+    let s = stacked_progress_bar(&[(80, '#'), (80, '~')], 100, 10);
+    assert_eq!(s, "[########~~]");
+    let s = stacked_progress_bar(&[(1, '#')], 0, 4);
+    assert_eq!(s, "[----]");
+}
+
 #[test]
 fn progress_bar_test() {
     let s = progress_bar(100, 100, 12);
     println!("{}", s);
+    assert_eq!(progress_bar(0, 0, 4), "[----]");
+    assert_eq!(percent(0.0, 0.0), 0.0);
 }
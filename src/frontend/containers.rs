@@ -0,0 +1,100 @@
+use super::{duration_to_time_string, human_bitps_string, human_byte_string, limit_string};
+use crate::{odd_even, threshold_color, EngineContainers, COLOR_DEFAULT, ICON_DOCKER};
+use std::io::Error;
+use std::time::Duration;
+
+// CPU usage (% of one CPU) colored as warning / critical
+const CONTAINER_CPU_WARNING: f64 = 50.0;
+const CONTAINER_CPU_CRITICAL: f64 = 90.0;
+
+/// running containers of Docker / Podman, empty without engine
+pub fn containers_to_string(
+    containers_snapshot0: &mut EngineContainers,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let containers_snapshot1 = EngineContainers::get()?;
+    let now = chrono::Utc::now().timestamp();
+
+    if containers_snapshot1.permission_denied {
+        s += &format!(
+            "{} {}  container engine socket not accessible (root or `docker` group required)\x1b[0m\n",
+            odd_even(0),
+            ICON_DOCKER
+        );
+    }
+
+    for (i, container) in containers_snapshot1.iter().enumerate() {
+        let start = containers_snapshot0
+            .iter()
+            .find(|start| start.id == container.id);
+
+        let uptime = match container.started_at {
+            Some(started_at) => {
+                duration_to_time_string(Duration::from_secs(now.saturating_sub(started_at) as u64))
+            }
+            None => "-".to_string(),
+        };
+
+        // no cgroup without `State.Pid` (inspect failed), veth needs root
+        let (cpu, memory) = match &container.cgroup {
+            Some(cgroup) => {
+                let cpu = cgroup.get_cpu_percent(start.and_then(|start| start.cgroup.as_ref()), dt);
+                let memory = cgroup.memory_current.unwrap_or_default();
+                (
+                    format!(
+                        "{}{:>5.1} %{}",
+                        threshold_color(cpu, CONTAINER_CPU_WARNING, CONTAINER_CPU_CRITICAL),
+                        cpu,
+                        COLOR_DEFAULT
+                    ),
+                    human_byte_string(memory.as_f64()),
+                )
+            }
+            None => (format!("{:>7}", "-"), format!("{:>11}", "-")),
+        };
+
+        // container view, host side veth receives what the container sends
+        let net = match (
+            container.veth_bytes,
+            start.and_then(|start| start.veth_bytes),
+        ) {
+            (Some((rx1, tx1)), Some((rx0, tx0))) => format!(
+                "rx: {}  tx: {}",
+                human_bitps_string(tx1.saturating_sub(tx0) as f64 / dt as f64),
+                human_bitps_string(rx1.saturating_sub(rx0) as f64 / dt as f64),
+            ),
+            _ => String::new(),
+        };
+
+        s += &format!(
+            "{} {}  {:<20} {:<25} {:<8} {:>10} {} {} {:<15} {}\x1b[0m\n",
+            odd_even(i + 1),
+            ICON_DOCKER,
+            limit_string(&container.name, 20),
+            limit_string(&container.image, 25),
+            container.state,
+            uptime,
+            cpu,
+            memory,
+            container.veth.clone().unwrap_or_default(),
+            net,
+        );
+    }
+
+    *containers_snapshot0 = containers_snapshot1;
+
+    Ok(s)
+}
+
+#[test]
+fn containers_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut snapshot0 = EngineContainers::get().unwrap();
+    thread::sleep(Duration::from_secs(dt));
+    let s = containers_to_string(&mut snapshot0, dt).unwrap();
+    print!("{}", s);
+}
@@ -1,28 +1,99 @@
 use super::{human_mhz_string, progress_bar};
-use crate::{get_cpuinfo, odd_even, CpuStats};
+use crate::{get_cpuinfo, odd_even, CpuIdle, CpuIdles, CpuStats, PowercapZones, ICON_POWER};
 use std::io::Error;
 
-pub fn proc_cpuinfo_to_string(cpu_snapshot0: &mut CpuStats) -> Result<String, Error> {
+/// get idle state residency string (e.g. `POLL   0 % C1   3 % C6  90 %`)
+pub fn cpuidle_to_string(idle1: &CpuIdle, idle0: Option<&CpuIdle>, dt: u64) -> String {
+    let mut s = String::new();
+
+    for (i, state1) in idle1.states.iter().enumerate() {
+        let residency = idle0
+            .and_then(|idle0| idle0.states.get(i))
+            .map(|state0| state1.get_residency(state0, dt).min(100.0))
+            .unwrap_or_default();
+        s += &format!(" {} {:>3.0} %", state1.name, residency);
+    }
+
+    s
+}
+
+pub fn proc_cpuinfo_to_string(
+    cpu_snapshot0: &mut CpuStats,
+    idle_snapshot0: &mut CpuIdles,
+    dt: u64,
+) -> Result<String, Error> {
     let mut s = String::new();
 
     let cpus = get_cpuinfo()?;
     let cpu_snapshot1 = CpuStats::get().unwrap();
     let p = cpu_snapshot1.get_performance(cpu_snapshot0);
+    let idle_snapshot1 = CpuIdles::get()?;
 
     for (i, cpu) in cpus.cpus.iter().enumerate() {
         let odd_even = odd_even(i);
+        let idle = idle_snapshot1
+            .get(&cpu.processor)
+            .map(|idle1| cpuidle_to_string(idle1, idle_snapshot0.get(&cpu.processor), dt))
+            .unwrap_or_default();
         s += &format!(
-            "{}   CPU #{:<3} {:<50}           {:<10} {} ({:>3} %)\n",
+            "{}   CPU #{:<3} {:<50}           {:<10} {} ({:>3} %){}\n",
             odd_even,
             cpu.processor,
             cpu.model_name,
             human_mhz_string(cpu.cpu_mhz),
             progress_bar(p[i + 1] as u64, 100, 20),
-            p[i + 1] as u64
+            p[i + 1] as u64,
+            idle
         );
     }
 
     *cpu_snapshot0 = cpu_snapshot1;
+    *idle_snapshot0 = idle_snapshot1;
+
+    Ok(s)
+}
+
+pub fn sys_class_powercap_to_string(
+    power_snapshot0: &mut PowercapZones,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let power_snapshot1 = PowercapZones::get()?;
+
+    if power_snapshot1.is_permission_denied() {
+        s += &format!(
+            "{} {}  Power: RAPL energy counters not readable (root required)\x1b[0m\n",
+            odd_even(0),
+            ICON_POWER
+        );
+    }
+
+    for (i, (name, zone1)) in power_snapshot1
+        .iter()
+        .filter(|(_, zone)| !zone.permission_denied)
+        .enumerate()
+    {
+        let power = power_snapshot0
+            .get(name)
+            .and_then(|zone0| zone1.get_power(zone0, dt))
+            .map(|w| format!("{:>7.2} W", w))
+            .unwrap_or_else(|| format!("{:>9}", "-"));
+
+        // subzones (e.g. `intel-rapl:0:0`) indented under package
+        let padding = " ".repeat(name.matches(':').count() * 2);
+
+        s += &format!(
+            "{} {}  {}{:<12} {}\x1b[0m\n",
+            odd_even(i),
+            ICON_POWER,
+            padding,
+            zone1.name,
+            power
+        );
+    }
+
+    *power_snapshot0 = power_snapshot1;
 
     Ok(s)
 }
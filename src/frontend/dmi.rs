@@ -1,22 +1,236 @@
-use crate::{get_dmi_info, odd_even, ICON_BIOS, ICON_MOTHERBOARD};
+use crate::{
+    chassis_kind_to_str, chassis_type_to_str, get_dmi_info, human_byte_string, limit_string,
+    memory_form_factor_to_str, memory_type_to_str, odd_even, DeviceTree, Smbios, ICON_BIOS,
+    ICON_MOTHERBOARD, ICON_RAM,
+};
 use std::io::Error;
 
+/// get optional DMI string, `-` if not set
+fn dmi_string(s: &Option<String>) -> &str {
+    s.as_deref().unwrap_or("-")
+}
+
+// NOTE: falls back to device tree model on boards without DMI (e.g. ARM)
 pub fn sys_class_dmi_to_string() -> Result<String, Error> {
     let mut s = String::new();
 
     let dmi_info = get_dmi_info()?;
 
-    let even = odd_even(0);
+    if dmi_info.is_empty() {
+        let device_tree = DeviceTree::get()?;
+        if !device_tree.is_empty() {
+            s += &format!(
+                "{} {}  Model: {} Compatible: {}\n",
+                odd_even(0),
+                ICON_MOTHERBOARD,
+                dmi_string(&device_tree.model),
+                device_tree.compatible.join(" ")
+            );
+        }
+        return Ok(s);
+    }
+
+    let chassis = dmi_info
+        .chassis_type
+        .map(|t| format!("{} ({})", chassis_type_to_str(t), chassis_kind_to_str(t)))
+        .unwrap_or_else(|| "-".to_string());
+    // serial and uuid are readable by root only
+    let serial = match (&dmi_info.product_serial, &dmi_info.product_uuid) {
+        (None, None) => String::new(),
+        (serial, uuid) => format!(" Serial: {} UUID: {}", dmi_string(serial), dmi_string(uuid)),
+    };
+    let chassis_serial = match &dmi_info.chassis_serial {
+        Some(serial) => format!(" Chassis Serial: {}", serial),
+        None => String::new(),
+    };
+    s += &format!(
+        "{} {}  System: {} {} {} Family: {} Chassis: {} {}{}{}\n",
+        odd_even(0),
+        ICON_MOTHERBOARD,
+        dmi_string(&dmi_info.sys_vendor),
+        dmi_string(&dmi_info.product_name),
+        dmi_string(&dmi_info.product_version),
+        dmi_string(&dmi_info.product_family),
+        dmi_string(&dmi_info.chassis_vendor),
+        chassis,
+        serial,
+        chassis_serial
+    );
+
+    let board_serial = match &dmi_info.board_serial {
+        Some(serial) => format!(" Serial: {}", serial),
+        None => String::new(),
+    };
     s += &format!(
-        "{} {}  Board Name: {} Vendor: {} Version: {}\n",
-        even, ICON_MOTHERBOARD, dmi_info.board_name, dmi_info.board_vendor, dmi_info.board_version
+        "{} {}  Board Name: {} Vendor: {} Version: {}{}\n",
+        odd_even(1),
+        ICON_MOTHERBOARD,
+        dmi_string(&dmi_info.board_name),
+        dmi_string(&dmi_info.board_vendor),
+        dmi_string(&dmi_info.board_version),
+        board_serial
     );
 
-    let odd = odd_even(1);
     s += &format!(
         "{} {}  BIOS Vendor: {} Version: {} Date: {}\n",
-        odd, ICON_BIOS, dmi_info.bios_vendor, dmi_info.bios_version, dmi_info.bios_date
+        odd_even(2),
+        ICON_BIOS,
+        dmi_string(&dmi_info.bios_vendor),
+        dmi_string(&dmi_info.bios_version),
+        dmi_string(&dmi_info.bios_date)
     );
 
     Ok(s)
 }
+
+#[test]
+fn sys_class_dmi_to_string_test() {
+    let s = sys_class_dmi_to_string().unwrap();
+    print!("{}", s);
+}
+
+// NOTE: tables are readable by root only
+pub fn sys_firmware_dmi_to_string() -> Result<String, Error> {
+    // system is shown by `sys_class_dmi_to_string` already, if sysfs has the product info
+    let dmi_info = get_dmi_info()?;
+    let show_system = dmi_info.sys_vendor.is_none() && dmi_info.product_name.is_none();
+
+    Ok(smbios_to_string(&Smbios::get()?, show_system))
+}
+
+/// system (optional), processors and memory modules of SMBIOS tables
+pub fn smbios_to_string(smbios: &Smbios, show_system: bool) -> String {
+    let mut s = String::new();
+
+    if smbios.permission_denied {
+        s += &format!(
+            "{} {}  Memory modules: SMBIOS tables not readable (root required)\x1b[0m\n",
+            odd_even(0),
+            ICON_RAM
+        );
+        return s;
+    }
+
+    let mut i = 0;
+
+    if let Some(system) = smbios.system.as_ref().filter(|_| show_system) {
+        let chassis = smbios
+            .chassis
+            .as_ref()
+            .map(|chassis| chassis_type_to_str(chassis.chassis_type))
+            .unwrap_or("-");
+        let version = smbios
+            .version
+            .map(|(major, minor)| format!("{}.{}", major, minor))
+            .unwrap_or_else(|| "-".to_string());
+        s += &format!(
+            "{} {}  System: {} {} Family: {} Chassis: {} SMBIOS: {}\x1b[0m\n",
+            odd_even(i),
+            ICON_MOTHERBOARD,
+            dmi_string(&system.manufacturer),
+            dmi_string(&system.product_name),
+            dmi_string(&system.family),
+            chassis,
+            version
+        );
+        i += 1;
+    }
+
+    // NOTE: unpopulated sockets have no core count
+    for processor in smbios.processors.iter().filter(|p| p.core_count.is_some()) {
+        s += &format!(
+            "{}   Socket: {:<8} {} Cores: {} Threads: {} Max: {} MHz\x1b[0m\n",
+            odd_even(i),
+            dmi_string(&processor.socket),
+            dmi_string(&processor.version),
+            processor.core_count.unwrap_or_default(),
+            processor.thread_count.unwrap_or_default(),
+            processor.max_speed.unwrap_or_default(),
+        );
+        i += 1;
+    }
+
+    let modules = smbios.memory_modules();
+    if modules.is_empty() {
+        return s;
+    }
+
+    let populated = modules.iter().filter(|m| m.size.is_some()).count();
+    let max_memory = smbios
+        .max_memory()
+        .map(|max| human_byte_string(max.as_f64()))
+        .unwrap_or_else(|| "-".to_string());
+    s += &format!(
+        "{} {}  Memory modules: {} / {} slots populated, installed: {} mapped: {} max: {}\x1b[0m\n",
+        odd_even(i),
+        ICON_RAM,
+        populated,
+        modules.len(),
+        human_byte_string(smbios.installed_memory().as_f64()),
+        human_byte_string(smbios.mapped_memory().as_f64()),
+        max_memory
+    );
+    i += 1;
+
+    for module in modules.iter() {
+        let slot = format!(
+            "{} {}",
+            dmi_string(&module.bank_locator),
+            dmi_string(&module.locator)
+        );
+        match module.size {
+            Some(size) => {
+                let speed = module
+                    .configured_speed
+                    .or(module.speed)
+                    .map(|speed| format!("{} MT/s", speed))
+                    .unwrap_or_default();
+                s += &format!(
+                    "{}   {:<24} {} {:<6} {:<7} {:<10} {} {}\x1b[0m\n",
+                    odd_even(i),
+                    limit_string(&slot, 24),
+                    human_byte_string(size.as_f64()),
+                    memory_type_to_str(module.memory_type),
+                    memory_form_factor_to_str(module.form_factor),
+                    speed,
+                    dmi_string(&module.manufacturer),
+                    dmi_string(&module.part_number),
+                );
+            }
+            None => {
+                s += &format!(
+                    "{}   {:<24} empty\x1b[0m\n",
+                    odd_even(i),
+                    limit_string(&slot, 24)
+                );
+            }
+        }
+        i += 1;
+    }
+
+    s
+}
+
+#[test]
+fn sys_firmware_dmi_to_string_test() {
+    let s = sys_firmware_dmi_to_string().unwrap();
+    print!("{}", s);
+}
+
+#[test]
+fn smbios_to_string_test() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata/smbios/dell-precision-3620");
+    let smbios = Smbios::get_from_path(&path).unwrap();
+    let s = smbios_to_string(&smbios, true);
+    print!("{}", s);
+    assert!(s.contains(
+        "System: Dell Inc. Precision Tower 3620 Family: Precision Chassis: Desktop SMBIOS: 3.0"
+    ));
+    assert!(s.contains("Memory modules: 2 / 4 slots populated"));
+
+    // sysfs DMI has the system already
+    let s = smbios_to_string(&smbios, false);
+    assert!(!s.contains("System:"));
+    assert!(s.contains("Memory modules: 2 / 4 slots populated"));
+}
@@ -5,6 +5,26 @@
 pub const ICON_MOTHERBOARD: &str = "󰚗";
 pub const ICON_BIOS: &str = "";
 pub const ICON_KERNEL: &str = "";
+pub const ICON_SHIELD: &str = "󰒃";
+pub const ICON_VIRTUALIZATION: &str = "";
+
+// distributions
+pub const ICON_LINUX: &str = "";
+pub const ICON_ALPINE: &str = "";
+pub const ICON_ARCH: &str = "";
+pub const ICON_CENTOS: &str = "";
+pub const ICON_DEBIAN: &str = "";
+pub const ICON_FEDORA: &str = "";
+pub const ICON_GENTOO: &str = "";
+pub const ICON_MINT: &str = "";
+pub const ICON_MANJARO: &str = "";
+pub const ICON_NIXOS: &str = "";
+pub const ICON_OPENSUSE: &str = "";
+pub const ICON_REDHAT: &str = "";
+pub const ICON_UBUNTU: &str = "";
+
+// cpu
+pub const ICON_POWER: &str = "󱐋";
 
 // ram
 pub const ICON_RAM: &str = "";
@@ -0,0 +1,193 @@
+use crate::{
+    limit_string, odd_even, threshold_color, Interrupts, ProcNetDevs, SoftIrqs, COLOR_DEFAULT,
+    COLOR_WARNING,
+};
+use std::io::Error;
+
+/// number of interrupt sources shown
+const INTERRUPTS_TOP: usize = 10;
+/// share of interrupts (%) on a single CPU colored as warning / critical
+const IRQ_SHARE_WARNING: f64 = 75.0;
+const IRQ_SHARE_CRITICAL: f64 = 90.0;
+/// share of NIC queues (%) served by a single CPU considered as imbalanced
+const NIC_QUEUES_IMBALANCE: f64 = 50.0;
+/// `▁▂▃▄▅▆▇█` share of interrupts per CPU
+const DISTRIBUTION_CHARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// get distribution over CPUs as a string, one char per CPU (e.g. `█▁ ▃`)
+pub fn distribution_to_string(deltas: &[u64]) -> String {
+    let max = deltas.iter().max().copied().unwrap_or_default();
+    deltas
+        .iter()
+        .map(|n| match max {
+            0 => DISTRIBUTION_CHARS[0],
+            _ => DISTRIBUTION_CHARS[((n * 8).div_ceil(max)) as usize],
+        })
+        .collect()
+}
+
+/// get CPU with most interrupts and its share in percent
+pub fn distribution_top_cpu(deltas: &[u64]) -> (usize, f64) {
+    let total: u64 = deltas.iter().sum();
+    let (cpu, max) = deltas
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, n)| **n)
+        .map(|(cpu, n)| (cpu, *n))
+        .unwrap_or_default();
+
+    match total {
+        0 => (cpu, 0.0),
+        _ => (cpu, max as f64 / total as f64 * 100.0),
+    }
+}
+
+/// NICs which most of the active queues are served by the same CPU between snapshots
+/// (e.g. `enp3s0: 4/4 queues on CPU0`)
+pub fn nic_queues_to_string(
+    irq_snapshot1: &Interrupts,
+    irq_snapshot0: &Interrupts,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let interfaces = ProcNetDevs::get()?;
+
+    let mut i = 0;
+    for name in interfaces.keys() {
+        // CPU with the most interrupts of each queue, idle queues skipped
+        let mut per_cpu = vec![0; irq_snapshot1.cpus];
+        let mut active = 0;
+        for irq in irq_snapshot1.get_nic_queues(name).iter() {
+            let Some(start) = irq_snapshot0.get(*irq) else {
+                continue;
+            };
+            let deltas = irq_snapshot1[*irq].get_delta(start);
+            if deltas.iter().sum::<u64>() == 0 {
+                continue;
+            }
+            let (cpu, _) = distribution_top_cpu(&deltas);
+            if let Some(n) = per_cpu.get_mut(cpu) {
+                *n += 1;
+            }
+            active += 1;
+        }
+        if active < 2 {
+            continue;
+        }
+        let (cpu, share) = distribution_top_cpu(&per_cpu);
+
+        if share > NIC_QUEUES_IMBALANCE {
+            s += &format!(
+                "{}   {}{:<15} {}/{} active queues handled by CPU{} (IRQ affinity imbalance){}\x1b[0m\n",
+                odd_even(i),
+                COLOR_WARNING,
+                name,
+                per_cpu[cpu],
+                active,
+                cpu,
+                COLOR_DEFAULT,
+            );
+            i += 1;
+        }
+    }
+
+    Ok(s)
+}
+
+pub fn proc_interrupts_to_string(
+    irq_snapshot0: &mut Interrupts,
+    softirq_snapshot0: &mut SoftIrqs,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let irq_snapshot1 = Interrupts::get()?;
+    let softirq_snapshot1 = SoftIrqs::get()?;
+
+    // top interrupt sources by rate
+    let mut top: Vec<(&String, Vec<u64>)> = irq_snapshot1
+        .iter()
+        .filter_map(|(irq, interrupt)| {
+            irq_snapshot0
+                .get(irq)
+                .map(|start| (irq, interrupt.get_delta(start)))
+        })
+        .filter(|(_, deltas)| deltas.iter().sum::<u64>() > 0)
+        .collect();
+    top.sort_by_key(|(_, deltas)| std::cmp::Reverse(deltas.iter().sum::<u64>()));
+
+    for (i, (irq, deltas)) in top.iter().take(INTERRUPTS_TOP).enumerate() {
+        let interrupt = &irq_snapshot1[*irq];
+        let rate = deltas.iter().sum::<u64>() / dt;
+        let (cpu, share) = distribution_top_cpu(deltas);
+
+        s += &format!(
+            "{}   IRQ {:<5} {:<22} {:<30} {:>8} /s  CPU{:<3} {}{:>3.0} %{} [{}]\x1b[0m\n",
+            odd_even(i),
+            irq,
+            limit_string(interrupt.chip.as_deref().unwrap_or_default(), 22),
+            limit_string(&interrupt.actions.join(","), 30),
+            rate,
+            cpu,
+            threshold_color(share, IRQ_SHARE_WARNING, IRQ_SHARE_CRITICAL),
+            share,
+            COLOR_DEFAULT,
+            distribution_to_string(deltas),
+        );
+    }
+
+    // softirqs with activity
+    let mut i = 0;
+    for (name, counts) in softirq_snapshot1.iter() {
+        let Some(start) = softirq_snapshot0.get(name) else {
+            continue;
+        };
+        let deltas: Vec<u64> = counts
+            .iter()
+            .zip(start.iter())
+            .map(|(n1, n0)| n1.saturating_sub(*n0))
+            .collect();
+        let total: u64 = deltas.iter().sum();
+        if total == 0 {
+            continue;
+        }
+        let (cpu, share) = distribution_top_cpu(&deltas);
+
+        s += &format!(
+            "{}   SoftIRQ {:<8} {:>62} /s  CPU{:<3} {:>3.0} % [{}]\x1b[0m\n",
+            odd_even(i),
+            name,
+            total / dt,
+            cpu,
+            share,
+            distribution_to_string(&deltas),
+        );
+        i += 1;
+    }
+
+    s += &nic_queues_to_string(&irq_snapshot1, irq_snapshot0)?;
+
+    *irq_snapshot0 = irq_snapshot1;
+    *softirq_snapshot0 = softirq_snapshot1;
+
+    Ok(s)
+}
+
+#[test]
+fn distribution_test() {
+    assert_eq!(distribution_to_string(&[8, 1, 0, 4]), "█▁ ▄");
+    assert_eq!(distribution_to_string(&[0, 0]), "  ");
+    assert_eq!(distribution_top_cpu(&[1, 6, 1]), (1, 75.0));
+}
+
+#[test]
+fn proc_interrupts_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut irq_snapshot0 = Interrupts::get().unwrap();
+    let mut softirq_snapshot0 = SoftIrqs::get().unwrap();
+    thread::sleep(Duration::from_secs(dt));
+    let s = proc_interrupts_to_string(&mut irq_snapshot0, &mut softirq_snapshot0, dt).unwrap();
+    print!("{}", s);
+}
@@ -1,7 +1,22 @@
-use crate::{odd_even, percent, MemInfo, ICON_GPU, ICON_RAM, ICON_SWAP};
+use crate::{
+    odd_even, percent, threshold_color, BlockDevicesMounts, Bytes, MemInfo, NumaNodes, SwapArea,
+    SwapType, Swaps, SysBlockInfos, VmStat, Zrams, Zswap, COLOR_CRITICAL, COLOR_DEFAULT, ICON_GPU,
+    ICON_RAM, ICON_SWAP, VMSTAT_OOM_KILL, VMSTAT_PGFAULT, VMSTAT_PGMAJFAULT, VMSTAT_PGSCAN_DIRECT,
+    VMSTAT_PGSCAN_KSWAPD, VMSTAT_PGSTEAL_DIRECT, VMSTAT_PGSTEAL_KSWAPD, VMSTAT_PSWPIN,
+    VMSTAT_PSWPOUT, VMSTAT_THP_COLLAPSE_ALLOC, VMSTAT_THP_FAULT_ALLOC,
+    VMSTAT_WORKINGSET_REFAULT_ANON,
+};
 use std::io::Error;
+use std::path::Path;
 
-use super::{human_byte_string, progress_bar};
+use super::{
+    block::get_block_device_icon, cpu_list_to_string, human_byte_string, limit_string,
+    progress_bar, stacked_progress_bar,
+};
+
+// direct reclaim scan rate (pages/s) colored as warning / critical
+const DIRECT_SCAN_WARNING: f64 = 1.0;
+const DIRECT_SCAN_CRITICAL: f64 = 1000.0;
 
 pub fn proc_meminfo_to_string() -> Result<String, Error> {
     let mut s = String::new();
@@ -14,7 +29,7 @@ pub fn proc_meminfo_to_string() -> Result<String, Error> {
 
     // percent used mem
     let mem_used = meminfo.mem_total - mem_available;
-    let percent_mem_used = percent(mem_used as f64, meminfo.mem_total as f64) as u64;
+    let percent_mem_used = percent(mem_used.as_f64(), meminfo.mem_total.as_f64()) as u64;
 
     let even = odd_even(0);
     // show free, total, percent used mem
@@ -22,15 +37,15 @@ pub fn proc_meminfo_to_string() -> Result<String, Error> {
         "{} {}  RAM                                                     {} / {} {} ({:>3} %)\n",
         even,
         ICON_RAM,
-        human_byte_string(mem_used as f64 * 1000.0),
-        human_byte_string(meminfo.mem_total as f64 * 1000.0),
-        progress_bar(mem_used, meminfo.mem_total, 20),
+        human_byte_string(mem_used.as_f64()),
+        human_byte_string(meminfo.mem_total.as_f64()),
+        progress_bar(mem_used.as_u64(), meminfo.mem_total.as_u64(), 20),
         percent_mem_used
     );
 
     // percent used swap
     let swap_used = meminfo.swap_total - meminfo.swap_free;
-    let percent_swap_used = percent(swap_used as f64, meminfo.swap_total as f64) as u64;
+    let percent_swap_used = percent(swap_used.as_f64(), meminfo.swap_total.as_f64()) as u64;
 
     let odd = odd_even(1);
     // show free, total, percent used swap
@@ -38,12 +53,53 @@ pub fn proc_meminfo_to_string() -> Result<String, Error> {
         "{} {}  Swap                                                    {} / {} {} ({:>3} %)\n",
         odd,
         ICON_SWAP,
-        human_byte_string(swap_used as f64 * 1000.0),
-        human_byte_string(meminfo.swap_total as f64 * 1000.0),
-        progress_bar(swap_used, meminfo.swap_total, 20),
+        human_byte_string(swap_used.as_f64()),
+        human_byte_string(meminfo.swap_total.as_f64()),
+        progress_bar(swap_used.as_u64(), meminfo.swap_total.as_u64(), 20),
         percent_swap_used
     );
 
+    // apps / buffers / cache / shmem / free breakdown
+    let b = |n: Bytes| human_byte_string(n.as_f64());
+    let even = odd_even(2);
+    s += &format!(
+        "{}   Apps: {} Buf: {} Cache: {} Shmem: {} Free: {}  {}\n",
+        even,
+        b(meminfo.apps()),
+        b(meminfo.buffers),
+        b(meminfo.cache()),
+        b(meminfo.shmem),
+        b(meminfo.mem_free),
+        stacked_progress_bar(
+            &[
+                (meminfo.apps().as_u64(), '#'),
+                (meminfo.buffers.as_u64(), '+'),
+                (meminfo.cache().as_u64(), '~'),
+                (meminfo.shmem.as_u64(), '%'),
+            ],
+            meminfo.mem_total.as_u64(),
+            20
+        ),
+    );
+
+    // commit ratio (Committed_AS / CommitLimit), may exceed 100 % with overcommit
+    let percent_commit =
+        percent(meminfo.committed_as.as_f64(), meminfo.commit_limit.as_f64()) as u64;
+    let odd = odd_even(3);
+    s += &format!(
+        "{}   Commit: {} / {} ({:>3} %) Slab: {} (reclaimable: {}) Dirty: {} Writeback: {} HugePages: {} / {}\n",
+        odd,
+        b(meminfo.committed_as),
+        b(meminfo.commit_limit),
+        percent_commit,
+        b(meminfo.slab),
+        b(meminfo.s_reclaimable),
+        b(meminfo.dirty),
+        b(meminfo.writeback),
+        meminfo.huge_pages_total.saturating_sub(meminfo.huge_pages_free),
+        meminfo.huge_pages_total,
+    );
+
     // s += &format!(
     // "{} {}  GPU                                                    \n",
     // even, ICON_GPU,
@@ -52,6 +108,251 @@ pub fn proc_meminfo_to_string() -> Result<String, Error> {
     Ok(s)
 }
 
+pub fn proc_vmstat_to_string(vmstat_snapshot0: &mut VmStat, dt: u64) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let vmstat_snapshot1 = VmStat::get()?;
+    let rate = |key: &str| vmstat_snapshot1.get_rate(vmstat_snapshot0, key, dt);
+    // pages/s -> B/s
+    let page_rate = |key: &str| human_byte_string(Bytes::from_pages(rate(key) as u64).as_f64());
+
+    let oom_kills = vmstat_snapshot1.get_counter(VMSTAT_OOM_KILL);
+    let oom_kills_new = oom_kills.saturating_sub(vmstat_snapshot0.get_counter(VMSTAT_OOM_KILL));
+
+    let even = odd_even(4);
+    s += &format!(
+        "{}   Swap in: {}/s out: {}/s  Faults: {:>8.0} /s major: {:>6.0} /s  {}OOM kills: {} (+{}){}\n",
+        even,
+        page_rate(VMSTAT_PSWPIN),
+        page_rate(VMSTAT_PSWPOUT),
+        rate(VMSTAT_PGFAULT),
+        rate(VMSTAT_PGMAJFAULT),
+        match oom_kills_new {
+            0 => COLOR_DEFAULT,
+            _ => COLOR_CRITICAL,
+        },
+        oom_kills,
+        oom_kills_new,
+        COLOR_DEFAULT,
+    );
+
+    // reclaim (pages/s), direct reclaim stalls allocating tasks
+    let odd = odd_even(5);
+    s += &format!(
+        "{}   Scan kswapd: {:>6.0} direct: {}{:>6.0}{} Steal kswapd: {:>6.0} direct: {:>6.0} /s  THP fault: {:>4.0} collapse: {:>4.0} /s  Refault anon: {:>5.0} file: {:>5.0} /s\n",
+        odd,
+        rate(VMSTAT_PGSCAN_KSWAPD),
+        threshold_color(
+            rate(VMSTAT_PGSCAN_DIRECT),
+            DIRECT_SCAN_WARNING,
+            DIRECT_SCAN_CRITICAL
+        ),
+        rate(VMSTAT_PGSCAN_DIRECT),
+        COLOR_DEFAULT,
+        rate(VMSTAT_PGSTEAL_KSWAPD),
+        rate(VMSTAT_PGSTEAL_DIRECT),
+        rate(VMSTAT_THP_FAULT_ALLOC),
+        rate(VMSTAT_THP_COLLAPSE_ALLOC),
+        rate(VMSTAT_WORKINGSET_REFAULT_ANON),
+        rate(vmstat_snapshot1.get_refault_file_key()),
+    );
+
+    *vmstat_snapshot0 = vmstat_snapshot1;
+
+    Ok(s)
+}
+
+/// get compression ratio string (e.g. `3.20x`)
+pub fn ratio_to_string(ratio: Option<f64>) -> String {
+    match ratio {
+        Some(ratio) => format!("{:>5.2}x", ratio),
+        None => format!("{:>6}", "-"),
+    }
+}
+
+pub fn zram_zswap_to_string() -> Result<String, Error> {
+    let mut s = String::new();
+
+    let zrams = Zrams::get()?;
+    let b = |n: Bytes| human_byte_string(n.as_f64());
+
+    // NOTE: uninitialized devices (`disksize` 0) are skipped
+    let mut i = 0;
+    for (name, zram) in zrams.iter().filter(|(_, zram)| zram.disksize.as_u64() > 0) {
+        let mm_stat = zram.mm_stat.unwrap_or_default();
+        s += &format!(
+            "{} {}  {:<8} {:<8} Orig: {} Compr: {} Used: {} / {} ({}) {}\n",
+            odd_even(i),
+            ICON_SWAP,
+            name,
+            zram.comp_algorithm.clone().unwrap_or_default(),
+            b(mm_stat.orig_data_size),
+            b(mm_stat.compr_data_size),
+            b(mm_stat.mem_used_total),
+            b(zram.disksize),
+            ratio_to_string(mm_stat.get_ratio()),
+            progress_bar(mm_stat.orig_data_size.as_u64(), zram.disksize.as_u64(), 20),
+        );
+        i += 1;
+    }
+
+    let zswap = Zswap::get()?;
+    if zswap.enabled == Some(true) {
+        let meminfo = MemInfo::get()?;
+        let ratio = match meminfo.zswap.as_u64() {
+            0 => None,
+            pool => Some(meminfo.zswapped.as_f64() / pool as f64),
+        };
+        s += &format!(
+            "{} {}  zswap    {:<8} Orig: {} Pool: {} ({}) max pool: {} % {}\n",
+            odd_even(i),
+            ICON_SWAP,
+            zswap.compressor.unwrap_or_default(),
+            b(meminfo.zswapped),
+            b(meminfo.zswap),
+            ratio_to_string(ratio),
+            zswap.max_pool_percent.unwrap_or_default(),
+            zswap.zpool.unwrap_or_default(),
+        );
+    }
+
+    Ok(s)
+}
+
+#[test]
+fn zram_zswap_to_string_test() {
+    let s = zram_zswap_to_string().unwrap();
+    print!("{}", s);
+}
+
+/// get swap area location string, partitions linked to block devices, files to their mount
+/// (e.g. `sda5`, `[vg-swap]`, `/home btrfs`)
+pub fn swap_area_location(
+    area: &SwapArea,
+    blocks: &SysBlockInfos,
+    mtab: &BlockDevicesMounts,
+) -> (String, String) {
+    match area.swap_type {
+        SwapType::Partition => match blocks.find_by_path(&area.filename) {
+            Some((name, device)) => (
+                get_block_device_icon(name, device).to_string(),
+                match &device.dm_name {
+                    Some(dm_name) => format!("[{}]", dm_name),
+                    None => name.to_string(),
+                },
+            ),
+            // e.g. zram, filtered out from block devices
+            None => (ICON_SWAP.to_string(), "-".to_string()),
+        },
+        // longest mount point containing the file
+        SwapType::File => match mtab
+            .values()
+            .filter(|mount| Path::new(&area.filename).starts_with(&mount.mnt_dir))
+            .max_by_key(|mount| mount.mnt_dir.len())
+        {
+            Some(mount) => (
+                ICON_SWAP.to_string(),
+                format!("{} {}", mount.mnt_dir, mount.mnt_type),
+            ),
+            None => (ICON_SWAP.to_string(), "-".to_string()),
+        },
+        SwapType::Other(_) => (ICON_SWAP.to_string(), "-".to_string()),
+    }
+}
+
+// NOTE: areas ordered by priority, the first one is filled first
+pub fn proc_swaps_to_string() -> Result<String, Error> {
+    let mut s = String::new();
+
+    let swaps = Swaps::get()?;
+    if swaps.is_empty() {
+        return Ok(s);
+    }
+
+    let blocks = SysBlockInfos::get()?;
+    let mtab = BlockDevicesMounts::get_from_mtab()?;
+
+    for (i, area) in swaps.by_priority().iter().enumerate() {
+        let (icon, location) = swap_area_location(area, &blocks, &mtab);
+        let swap_type = match &area.swap_type {
+            SwapType::Partition => "partition",
+            SwapType::File => "file",
+            SwapType::Other(swap_type) => swap_type,
+        };
+
+        s += &format!(
+            "{} {}  {:<20} {:<9} prio: {:>5}  {:<18} {} / {} {} ({:>3} %)\x1b[0m\n",
+            odd_even(i),
+            icon,
+            limit_string(&area.filename, 20),
+            swap_type,
+            area.priority,
+            limit_string(&location, 18),
+            human_byte_string(area.used.as_f64()),
+            human_byte_string(area.size.as_f64()),
+            progress_bar(area.used.as_u64(), area.size.as_u64(), 20),
+            percent(area.used.as_f64(), area.size.as_f64()) as u64,
+        );
+    }
+
+    Ok(s)
+}
+
+#[test]
+fn proc_swaps_to_string_test() {
+    let s = proc_swaps_to_string().unwrap();
+    print!("{}", s);
+}
+
+// NOTE: empty on single node machines
+pub fn sys_devices_system_node_to_string(
+    numa_snapshot0: &mut NumaNodes,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let numa_snapshot1 = NumaNodes::get()?;
+
+    if numa_snapshot1.len() > 1 {
+        for (i, (n, node1)) in numa_snapshot1.iter().enumerate() {
+            let numastat0 = numa_snapshot0
+                .get(n)
+                .map(|node0| node0.numastat)
+                .unwrap_or(node1.numastat);
+            // pages/s
+            let rate = |end: u64, start: u64| end.saturating_sub(start) / dt;
+
+            let total = node1.meminfo.mem_total;
+            let used = total - node1.meminfo.mem_free;
+
+            s += &format!(
+                "{} {}  Node {:<3} CPUs: {:<16} {} / {} {} ({:>3} %)  hit: {:>7} miss: {:>7} foreign: {:>7} /s  distance: {}\n",
+                odd_even(i),
+                ICON_RAM,
+                n,
+                limit_string(&cpu_list_to_string(&node1.cpus), 16),
+                human_byte_string(used.as_f64()),
+                human_byte_string(total.as_f64()),
+                progress_bar(used.as_u64(), total.as_u64(), 20),
+                percent(used.as_f64(), total.as_f64()) as u64,
+                rate(node1.numastat.numa_hit, numastat0.numa_hit),
+                rate(node1.numastat.numa_miss, numastat0.numa_miss),
+                rate(node1.numastat.numa_foreign, numastat0.numa_foreign),
+                node1
+                    .distance
+                    .iter()
+                    .map(|d| d.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            );
+        }
+    }
+
+    *numa_snapshot0 = numa_snapshot1;
+
+    Ok(s)
+}
+
 fn bench_meminfo() {
     let s = proc_meminfo_to_string().unwrap();
     println!("{}", s);
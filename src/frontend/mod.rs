@@ -1,19 +1,33 @@
 pub mod block;
+pub mod cgroup;
 pub mod common;
+pub mod containers;
 pub mod cpu;
 pub mod dmi;
 pub mod icons;
+pub mod interrupts;
 pub mod memory;
 pub mod network;
+pub mod pressure;
+pub mod processes;
 pub mod uname;
 pub mod uptime;
+pub mod virtualization;
+pub mod vulnerabilities;
 
 pub use block::*;
+pub use cgroup::*;
 pub use common::*;
+pub use containers::*;
 pub use cpu::*;
 pub use dmi::*;
 pub use icons::*;
+pub use interrupts::*;
 pub use memory::*;
 pub use network::*;
+pub use pressure::*;
+pub use processes::*;
 pub use uname::*;
 pub use uptime::*;
+pub use virtualization::*;
+pub use vulnerabilities::*;
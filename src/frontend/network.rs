@@ -1,8 +1,12 @@
 use crate::odd_even;
 use crate::{
-    NetInterfaces, ProcNetDevs, ICON_BR, ICON_DOCKER, ICON_ETH, ICON_LO, ICON_VETH, ICON_WIFI,
+    get_socket_owners, NetInterfaces, NetSockets, Passwd, ProcNetDevs, Process, SocketProtocol,
+    ICON_BR, ICON_DOCKER, ICON_ETH, ICON_LO, ICON_VETH, ICON_WIFI, PROC,
 };
 use std::io::Error;
+use std::path::Path;
+
+use super::limit_string;
 
 use super::human_bitps_string;
 use super::human_byte_string;
@@ -79,3 +83,63 @@ pub fn sys_class_net_to_string(net_snapshot0: &mut ProcNetDevs, dt: u64) -> Resu
 
     Ok(s)
 }
+
+/// listening sockets with owner process and number of connections (TCP, unix)
+pub fn listening_sockets_to_string() -> Result<String, Error> {
+    let mut s = String::new();
+
+    let sockets = NetSockets::get()?;
+    let owners = get_socket_owners();
+    // NOTE: uids are shown, if `/etc/passwd` is not readable
+    let passwd = Passwd::get().unwrap_or_default();
+
+    s += &format!(
+        "{}   {:<5} {:<40} {:>7} {:<10} {:>5}  Command (listening sockets)\x1b[0m\n",
+        odd_even(0),
+        "Proto",
+        "Address",
+        "PID",
+        "User",
+        "Conn"
+    );
+
+    for (i, (socket, connections)) in sockets.get_listeners().iter().enumerate() {
+        // NOTE: owners of other users' sockets are not known without root
+        let process = owners
+            .get(&socket.inode)
+            .and_then(|pid| Process::get(Path::new(PROC).join(pid.to_string())).ok());
+
+        let (pid, command) = match &process {
+            Some(process) => (process.pid.to_string(), process.get_command()),
+            None => ("-".to_string(), String::new()),
+        };
+        let user = match (&process, socket.protocol) {
+            (Some(process), _) => passwd.get_name(process.status.uid),
+            (None, SocketProtocol::Unix) => "-".to_string(),
+            (None, _) => passwd.get_name(socket.uid),
+        };
+        let connections = match socket.protocol {
+            SocketProtocol::Udp | SocketProtocol::Udp6 => "-".to_string(),
+            _ => connections.to_string(),
+        };
+
+        s += &format!(
+            "{}   {:<5} {:<40} {:>7} {:<10} {:>5}  {}\x1b[0m\n",
+            odd_even(i + 1),
+            socket.protocol.name(),
+            limit_string(&socket.get_local(), 40),
+            pid,
+            limit_string(&user, 10),
+            connections,
+            limit_string(&command, 60),
+        );
+    }
+
+    Ok(s)
+}
+
+#[test]
+fn listening_sockets_to_string_test() {
+    let s = listening_sockets_to_string().unwrap();
+    print!("{}", s);
+}
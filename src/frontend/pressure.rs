@@ -0,0 +1,87 @@
+use crate::{odd_even, progress_bar, threshold_color, Pressure, Pressures, COLOR_DEFAULT};
+use std::io::Error;
+
+// avg10 stall share (%) colored as warning / critical
+const PRESSURE_WARNING: f64 = 10.0;
+const PRESSURE_CRITICAL: f64 = 40.0;
+
+/// get `some` or `full` pressure line string (e.g. `some:   4.45   3.33   1.01`)
+pub fn pressure_line_to_string(kind: &str, pressure: &Pressure, full: bool) -> String {
+    let line = if full { pressure.full } else { pressure.some };
+    match line {
+        Some(line) => format!(
+            "{}{}: {:>6.2} {:>6.2} {:>6.2}{}",
+            threshold_color(line.avg10, PRESSURE_WARNING, PRESSURE_CRITICAL),
+            kind,
+            line.avg10,
+            line.avg60,
+            line.avg300,
+            COLOR_DEFAULT
+        ),
+        None => format!("{}:      -      -      -", kind),
+    }
+}
+
+pub fn proc_pressure_to_string(
+    pressure_snapshot0: &mut Pressures,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let pressure_snapshot1 = Pressures::get()?;
+
+    // degrade gracefully, kernel without PSI (e.g. < 4.20 or `psi=0`)
+    if !pressure_snapshot1.is_supported() {
+        s += &format!("{}   PSI not available\x1b[0m\n", odd_even(0));
+        return Ok(s);
+    }
+
+    let resources = [
+        ("CPU", pressure_snapshot1.cpu, pressure_snapshot0.cpu),
+        (
+            "Memory",
+            pressure_snapshot1.memory,
+            pressure_snapshot0.memory,
+        ),
+        ("IO", pressure_snapshot1.io, pressure_snapshot0.io),
+        ("IRQ", pressure_snapshot1.irq, pressure_snapshot0.irq),
+    ];
+
+    for (i, (name, pressure1, pressure0)) in resources.iter().enumerate() {
+        let Some(pressure1) = pressure1 else {
+            continue;
+        };
+
+        // stall share during last `dt`, `some` if available (irq: `full` only)
+        let line1 = pressure1.some.or(pressure1.full).unwrap_or_default();
+        let line0 = pressure0.and_then(|p| p.some.or(p.full)).unwrap_or(line1);
+        let stall = line1.get_stall_percent(&line0, dt).min(100.0);
+
+        s += &format!(
+            "{}   {:<8} {}  {}  stall: {}{:>6.2} %{} {}\x1b[0m\n",
+            odd_even(i),
+            name,
+            pressure_line_to_string("some", pressure1, false),
+            pressure_line_to_string("full", pressure1, true),
+            threshold_color(stall, PRESSURE_WARNING, PRESSURE_CRITICAL),
+            stall,
+            COLOR_DEFAULT,
+            progress_bar(stall as u64, 100, 20),
+        );
+    }
+
+    *pressure_snapshot0 = pressure_snapshot1;
+
+    Ok(s)
+}
+
+#[test]
+fn proc_pressure_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut snapshot0 = Pressures::get().unwrap();
+    thread::sleep(Duration::from_secs(dt));
+    let s = proc_pressure_to_string(&mut snapshot0, dt).unwrap();
+    print!("{}", s);
+}
@@ -0,0 +1,617 @@
+use super::{
+    duration_to_time_string, human_byte_string, human_byteps_string, limit_string, threshold_color,
+};
+use crate::{
+    odd_even, Bytes, MemInfo, MemoryTrend, MemoryTrends, Passwd, ProcList, Process, ProcessIoRate,
+    COLOR_DEFAULT,
+};
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// number of processes shown by default
+pub const PROCESSES_TOP: usize = 10;
+
+// CPU usage (% of one CPU) colored as warning / critical
+const PROCESS_CPU_WARNING: f64 = 50.0;
+const PROCESS_CPU_CRITICAL: f64 = 90.0;
+
+/// window of the memory growth trend by default
+pub const MEMORY_TREND_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// growth (B/s) below is not shown, 1 MiB/h
+const MEMORY_GROWTH_MIN: f64 = 1024.0 * 1024.0 / 3600.0;
+// growth (MiB/h) colored as warning / critical
+const MEMORY_GROWTH_WARNING: f64 = 10.0;
+const MEMORY_GROWTH_CRITICAL: f64 = 100.0;
+
+/// sort key of the top processes list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessSortKey {
+    #[default]
+    Cpu,
+    Rss,
+    Swap,
+}
+
+impl FromStr for ProcessSortKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cpu" => Ok(ProcessSortKey::Cpu),
+            "rss" | "mem" => Ok(ProcessSortKey::Rss),
+            "swap" => Ok(ProcessSortKey::Swap),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("unknown sort key `{}` (cpu, rss, swap)", s),
+            )),
+        }
+    }
+}
+
+/// options of the processes section (e.g. `--sort rss --top 20 --tree`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessOptions {
+    pub sort: ProcessSortKey,
+    /// number of processes in top lists
+    pub top: usize,
+    /// process tree instead of top processes list
+    pub tree: bool,
+    /// window of the memory growth trend
+    pub trend_window: Duration,
+}
+
+impl Default for ProcessOptions {
+    fn default() -> Self {
+        ProcessOptions {
+            sort: ProcessSortKey::default(),
+            top: PROCESSES_TOP,
+            tree: false,
+            trend_window: MEMORY_TREND_WINDOW,
+        }
+    }
+}
+
+/// processes with CPU usage between snapshots, sorted by given key (descending)
+pub fn get_top_processes<'a>(
+    ps_snapshot1: &'a ProcList,
+    ps_snapshot0: &ProcList,
+    sort: ProcessSortKey,
+    dt: u64,
+) -> Vec<(&'a Process, f64)> {
+    let mut top: Vec<(&Process, f64)> = ps_snapshot1
+        .values()
+        .map(|process| {
+            let cpu = process.get_cpu_percent(ps_snapshot0.get(&process.pid), dt);
+            (process, cpu)
+        })
+        .collect();
+
+    match sort {
+        ProcessSortKey::Cpu => top.sort_by(|(_, a), (_, b)| b.total_cmp(a)),
+        ProcessSortKey::Rss => top.sort_by_key(|(p, _)| std::cmp::Reverse(p.status.vm_rss)),
+        ProcessSortKey::Swap => top.sort_by_key(|(p, _)| std::cmp::Reverse(p.status.vm_swap)),
+    }
+
+    top
+}
+
+pub fn top_processes_to_string(
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    passwd: &Passwd,
+    sort: ProcessSortKey,
+    n: usize,
+    dt: u64,
+) -> String {
+    let mut s = String::new();
+
+    let top = get_top_processes(ps_snapshot1, ps_snapshot0, sort, dt);
+
+    s += &format!(
+        "{}   {:>7} {:<10} S {:>4} {:>7}   {:>11} {:>11}  Command (sorted by {:?}, {} processes)\x1b[0m\n",
+        odd_even(0),
+        "PID",
+        "User",
+        "Thr",
+        "CPU",
+        "RSS",
+        "Swap",
+        sort,
+        ps_snapshot1.len()
+    );
+
+    for (i, (process, cpu)) in top.iter().take(n).enumerate() {
+        s += &format!(
+            "{}   {:>7} {:<10} {} {:>4} {}{:>5.1} %{} {} {}  {}\x1b[0m\n",
+            odd_even(i + 1),
+            process.pid,
+            limit_string(&passwd.get_name(process.status.uid), 10),
+            process.stat.state,
+            process.status.threads,
+            threshold_color(*cpu, PROCESS_CPU_WARNING, PROCESS_CPU_CRITICAL),
+            cpu,
+            COLOR_DEFAULT,
+            human_byte_string(process.status.vm_rss.as_f64()),
+            human_byte_string(process.status.vm_swap.as_f64()),
+            limit_string(&process.get_command(), 60),
+        );
+    }
+
+    s
+}
+
+// processes by storage read + write throughput, idle processes skipped
+pub fn io_top_to_string(
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    passwd: &Passwd,
+    n: usize,
+    dt: u64,
+) -> String {
+    let mut s = String::new();
+
+    let mut top: Vec<(&Process, ProcessIoRate)> = ps_snapshot1
+        .values()
+        .filter_map(|process| {
+            let rate = process.get_io_rate(ps_snapshot0.get(&process.pid), dt)?;
+            Some((process, rate))
+        })
+        .filter(|(_, rate)| rate.total() > 0.0 || rate.cancelled_write > 0.0)
+        .collect();
+    top.sort_by(|(_, a), (_, b)| b.total().total_cmp(&a.total()));
+
+    let denied = ps_snapshot1
+        .values()
+        .filter(|process| process.io_permission_denied)
+        .count();
+
+    s += &format!(
+        "{}   {:>7} {:<10} {:>12} {:>12} {:>12} {:>9} {:>9}  Command (I/O top{})\x1b[0m\n",
+        odd_even(0),
+        "PID",
+        "User",
+        "Read",
+        "Write",
+        "Cancelled",
+        "syscr",
+        "syscw",
+        match denied {
+            0 => String::new(),
+            denied => format!(", {} processes not readable, root required", denied),
+        }
+    );
+
+    for (i, (process, rate)) in top.iter().take(n).enumerate() {
+        s += &format!(
+            "{}   {:>7} {:<10} {} {} {} {:>7.0}/s {:>7.0}/s  {}\x1b[0m\n",
+            odd_even(i + 1),
+            process.pid,
+            limit_string(&passwd.get_name(process.status.uid), 10),
+            human_byteps_string(rate.read),
+            human_byteps_string(rate.write),
+            human_byteps_string(rate.cancelled_write),
+            rate.syscr,
+            rate.syscw,
+            limit_string(&process.get_command(), 60),
+        );
+    }
+
+    s
+}
+
+/// CPU usage and RSS of process and all descendants, stored for each process of the subtree
+fn get_subtree_usage(
+    pid: u32,
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    children: &BTreeMap<u32, Vec<u32>>,
+    dt: u64,
+    usage: &mut BTreeMap<u32, (f64, Bytes)>,
+) -> (f64, Bytes) {
+    let Some(process) = ps_snapshot1.get(&pid) else {
+        return (0.0, Bytes(0));
+    };
+
+    let mut cpu = process.get_cpu_percent(ps_snapshot0.get(&pid), dt);
+    let mut rss = process.status.vm_rss;
+    for child in children.get(&pid).into_iter().flatten() {
+        let (child_cpu, child_rss) =
+            get_subtree_usage(*child, ps_snapshot1, ps_snapshot0, children, dt, usage);
+        cpu += child_cpu;
+        rss += child_rss;
+    }
+
+    usage.insert(pid, (cpu, rss));
+    (cpu, rss)
+}
+
+/// children sorted by subtree CPU usage (descending), kernel threads skipped
+fn get_sorted_children(
+    pids: &[u32],
+    ps_snapshot1: &ProcList,
+    usage: &BTreeMap<u32, (f64, Bytes)>,
+) -> Vec<u32> {
+    let mut pids: Vec<u32> = pids
+        .iter()
+        .filter(|pid| {
+            ps_snapshot1
+                .get(pid)
+                .is_some_and(|process| !process.is_kernel_thread())
+        })
+        .copied()
+        .collect();
+    let cpu = |pid: &u32| usage.get(pid).map(|(cpu, _)| *cpu).unwrap_or_default();
+    pids.sort_by(|a, b| cpu(b).total_cmp(&cpu(a)));
+    pids
+}
+
+pub fn print_process_tree(
+    padding: &str,
+    i: &mut usize,
+    pid: u32,
+    ps_snapshot1: &ProcList,
+    children: &BTreeMap<u32, Vec<u32>>,
+    usage: &BTreeMap<u32, (f64, Bytes)>,
+    passwd: &Passwd,
+) -> String {
+    let mut s = String::new();
+
+    let Some(process) = ps_snapshot1.get(&pid) else {
+        return s;
+    };
+    let (cpu, rss) = usage.get(&pid).copied().unwrap_or_default();
+
+    // tree guide (e.g. `  └ make`), roots without
+    let command = match padding.is_empty() {
+        true => process.get_command(),
+        false => format!("{}└ {}", padding, process.get_command()),
+    };
+
+    s += &format!(
+        "{}   {:>7} {:<10} {} {:>4} {}{:>5.1} %{} {}  {}\x1b[0m\n",
+        odd_even(*i),
+        process.pid,
+        limit_string(&passwd.get_name(process.status.uid), 10),
+        process.stat.state,
+        process.status.threads,
+        threshold_color(cpu, PROCESS_CPU_WARNING, PROCESS_CPU_CRITICAL),
+        cpu,
+        COLOR_DEFAULT,
+        human_byte_string(rss.as_f64()),
+        limit_string(&command, 60),
+    );
+
+    let padding = match padding.is_empty() {
+        true => " ".to_string(),
+        false => format!("{}  ", padding),
+    };
+    let pids = children.get(&pid).map(Vec::as_slice).unwrap_or_default();
+    for child in get_sorted_children(pids, ps_snapshot1, usage) {
+        *i += 1;
+        s += &print_process_tree(&padding, i, child, ps_snapshot1, children, usage, passwd);
+    }
+
+    s
+}
+
+/// process tree with CPU usage and RSS of each subtree, kernel threads skipped
+pub fn process_tree_to_string(
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    passwd: &Passwd,
+    dt: u64,
+) -> String {
+    let mut s = String::new();
+
+    let children = ps_snapshot1.get_children();
+    let roots = ps_snapshot1.get_roots();
+
+    let mut usage = BTreeMap::new();
+    for pid in roots.iter() {
+        get_subtree_usage(*pid, ps_snapshot1, ps_snapshot0, &children, dt, &mut usage);
+    }
+
+    s += &format!(
+        "{}   {:>7} {:<10} S {:>4} {:>7}   {:>11}  Command (tree, CPU and RSS of subtree, {} processes)\x1b[0m\n",
+        odd_even(0),
+        "PID",
+        "User",
+        "Thr",
+        "CPU",
+        "RSS",
+        ps_snapshot1.len()
+    );
+
+    let mut i = 1;
+    for pid in get_sorted_children(&roots, ps_snapshot1, &usage) {
+        s += &print_process_tree("", &mut i, pid, ps_snapshot1, &children, &usage, passwd);
+        i += 1;
+    }
+
+    s
+}
+
+/// resource usage of all processes of a user
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UserUsage {
+    pub processes: usize,
+    /// CPU usage (% of one CPU)
+    pub cpu: f64,
+    pub rss: Bytes,
+    pub swap: Bytes,
+    /// storage read + write (B/s) of processes with readable `io`
+    pub io: f64,
+    /// processes with `io` not readable (root required)
+    pub io_permission_denied: usize,
+}
+
+/// usage by real uid (`BTreeMap<uid, UserUsage>`), kernel threads count as root
+pub fn get_user_usage(
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    dt: u64,
+) -> BTreeMap<u32, UserUsage> {
+    let mut users: BTreeMap<u32, UserUsage> = BTreeMap::new();
+
+    for process in ps_snapshot1.values() {
+        let start = ps_snapshot0.get(&process.pid);
+        let user = users.entry(process.status.uid).or_default();
+        user.processes += 1;
+        user.cpu += process.get_cpu_percent(start, dt);
+        user.rss += process.status.vm_rss;
+        user.swap += process.status.vm_swap;
+        // NOTE: new processes have no rate yet, not counted as denied
+        if let Some(rate) = process.get_io_rate(start, dt) {
+            user.io += rate.total();
+        }
+        if process.io_permission_denied {
+            user.io_permission_denied += 1;
+        }
+    }
+
+    users
+}
+
+/// users sorted by given key (descending)
+pub fn users_to_string(
+    ps_snapshot1: &ProcList,
+    ps_snapshot0: &ProcList,
+    passwd: &Passwd,
+    sort: ProcessSortKey,
+    dt: u64,
+) -> String {
+    let mut s = String::new();
+
+    let mut users: Vec<(u32, UserUsage)> = get_user_usage(ps_snapshot1, ps_snapshot0, dt)
+        .into_iter()
+        .collect();
+    match sort {
+        ProcessSortKey::Cpu => users.sort_by(|(_, a), (_, b)| b.cpu.total_cmp(&a.cpu)),
+        ProcessSortKey::Rss => users.sort_by_key(|(_, user)| std::cmp::Reverse(user.rss)),
+        ProcessSortKey::Swap => users.sort_by_key(|(_, user)| std::cmp::Reverse(user.swap)),
+    }
+
+    s += &format!(
+        "{}   {:<18} {:>5} {:>7}   {:>11} {:>11} {:>12}  (users, sorted by {:?})\x1b[0m\n",
+        odd_even(0),
+        "User",
+        "Procs",
+        "CPU",
+        "RSS",
+        "Swap",
+        "I/O",
+        sort
+    );
+
+    for (i, (uid, user)) in users.iter().enumerate() {
+        // NOTE: other users' I/O is not readable without root
+        let io = match user.io_permission_denied == user.processes {
+            true => format!("{:>12}", "-"),
+            false => human_byteps_string(user.io),
+        };
+        s += &format!(
+            "{}   {:<18} {:>5} {}{:>5.1} %{} {} {} {}\x1b[0m\n",
+            odd_even(i + 1),
+            limit_string(&passwd.get_name(*uid), 18),
+            user.processes,
+            threshold_color(user.cpu, PROCESS_CPU_WARNING, PROCESS_CPU_CRITICAL),
+            user.cpu,
+            COLOR_DEFAULT,
+            human_byte_string(user.rss.as_f64()),
+            human_byte_string(user.swap.as_f64()),
+            io,
+        );
+    }
+
+    s
+}
+
+/// processes with growing memory (PSS, RSS if not readable), fastest first,
+/// with time until `MemAvailable` is used up at this growth
+pub fn growing_processes_to_string(
+    trends: &MemoryTrends,
+    ps_snapshot1: &ProcList,
+    passwd: &Passwd,
+    mem_available: Bytes,
+    n: usize,
+) -> String {
+    let mut s = String::new();
+
+    // NOTE: trends are updated once per sample interval, pids may have exited or been reused since
+    let growing: Vec<(&Process, &MemoryTrend, f64)> = trends
+        .get_growing(MEMORY_GROWTH_MIN)
+        .into_iter()
+        .filter_map(|(trend, slope)| {
+            let process = ps_snapshot1.get(&trend.pid)?;
+            (process.stat.starttime == trend.starttime).then_some((process, trend, slope))
+        })
+        .collect();
+
+    s += &format!(
+        "{}   {:>7} {:<10} {:>11} {:>11} {:>10} {:>12}  Command (growing, {} of {} min window)\x1b[0m\n",
+        odd_even(0),
+        "PID",
+        "User",
+        "RSS",
+        "PSS",
+        "MiB/h",
+        "Exhausted",
+        growing.len(),
+        trends.window.as_secs() / 60,
+    );
+
+    for (i, (process, trend, slope)) in growing.iter().take(n).enumerate() {
+        let last = trend.get_last().copied().unwrap_or_default();
+        // NOTE: PSS of other users' processes is not readable without root
+        let pss = match last.pss {
+            Some(pss) => human_byte_string(pss.as_f64()),
+            None => format!("{:>11}", "-"),
+        };
+        let growth = slope * 3600.0 / (1024.0 * 1024.0);
+        let exhausted =
+            duration_to_time_string(Duration::from_secs_f64(mem_available.as_f64() / slope));
+
+        s += &format!(
+            "{}   {:>7} {:<10} {} {} {}{:>10.1}{} {:>12}  {}\x1b[0m\n",
+            odd_even(i + 1),
+            trend.pid,
+            limit_string(&passwd.get_name(process.status.uid), 10),
+            human_byte_string(last.rss.as_f64()),
+            pss,
+            threshold_color(growth, MEMORY_GROWTH_WARNING, MEMORY_GROWTH_CRITICAL),
+            growth,
+            COLOR_DEFAULT,
+            exhausted,
+            limit_string(&process.get_command(), 60),
+        );
+    }
+
+    s
+}
+
+pub fn processes_to_string(
+    ps_snapshot0: &mut ProcList,
+    trends: &mut MemoryTrends,
+    options: &ProcessOptions,
+    dt: u64,
+) -> Result<String, Error> {
+    let mut s = String::new();
+
+    let ps_snapshot1 = ProcList::get()?;
+    trends.update(&ps_snapshot1);
+    let mem_available = MemInfo::get()?.mem_available;
+    // NOTE: uids are shown, if `/etc/passwd` is not readable
+    let passwd = Passwd::get().unwrap_or_default();
+
+    s += &match options.tree {
+        true => process_tree_to_string(&ps_snapshot1, ps_snapshot0, &passwd, dt),
+        false => top_processes_to_string(
+            &ps_snapshot1,
+            ps_snapshot0,
+            &passwd,
+            options.sort,
+            options.top,
+            dt,
+        ),
+    };
+    s += &io_top_to_string(&ps_snapshot1, ps_snapshot0, &passwd, options.top, dt);
+    s += &users_to_string(&ps_snapshot1, ps_snapshot0, &passwd, options.sort, dt);
+    s += &growing_processes_to_string(trends, &ps_snapshot1, &passwd, mem_available, options.top);
+
+    *ps_snapshot0 = ps_snapshot1;
+
+    Ok(s)
+}
+
+#[test]
+fn processes_to_string_test() {
+    use std::{thread, time::Duration};
+
+    let dt = 1;
+    let mut snapshot0 = ProcList::get().unwrap();
+    let mut trends = MemoryTrends::new(MEMORY_TREND_WINDOW);
+    thread::sleep(Duration::from_secs(dt));
+    let s =
+        processes_to_string(&mut snapshot0, &mut trends, &ProcessOptions::default(), dt).unwrap();
+    print!("{}", s);
+    let options = ProcessOptions {
+        tree: true,
+        ..Default::default()
+    };
+    let s = processes_to_string(&mut snapshot0, &mut trends, &options, dt).unwrap();
+    print!("{}", s);
+
+    assert_eq!(
+        "rss".parse::<ProcessSortKey>().unwrap(),
+        ProcessSortKey::Rss
+    );
+    assert!("pid".parse::<ProcessSortKey>().is_err());
+}
+
+#[test]
+fn get_user_usage_test() {
+    let mut ps = ProcList::default();
+    for (pid, uid, rss) in [(1, 0, 8), (100, 1000, 100), (101, 1000, 50), (200, 33, 20)] {
+        // `io` of pid 101 missing, but not denied (e.g. exited meanwhile)
+        let mut process = Process {
+            pid,
+            io_permission_denied: pid != 101,
+            ..Default::default()
+        };
+        process.status.uid = uid;
+        process.status.vm_rss = Bytes::from_kib(rss);
+        ps.insert(pid, process);
+    }
+
+    let users = get_user_usage(&ps, &ps, 1);
+    assert_eq!(users.len(), 3);
+    let user = users.get(&1000).unwrap();
+    assert_eq!(user.processes, 2);
+    assert_eq!(user.rss, Bytes::from_kib(150));
+    assert_eq!(user.cpu, 0.0);
+    assert_eq!(user.io_permission_denied, 1);
+}
+
+#[test]
+fn growing_processes_to_string_test() {
+    use crate::MemorySample;
+
+    let mut trends = MemoryTrends::new(MEMORY_TREND_WINDOW);
+    // pids 10 and 20 grow by 1 MiB/min
+    for i in 0..5 {
+        let time = i as f64 * 60.0;
+        let sample = MemorySample {
+            time,
+            rss: Bytes::from_kib(1024 * (100 + i)),
+            pss: None,
+        };
+        trends.add_samples(time, &[(10, 100, sample), (20, 200, sample)]);
+    }
+
+    // pid 20 reused by another process since the last sample
+    let mut ps = ProcList::default();
+    for (pid, starttime) in [(10, 100), (20, 999)] {
+        let mut process = Process {
+            pid,
+            ..Default::default()
+        };
+        process.stat.starttime = starttime;
+        ps.insert(pid, process);
+    }
+
+    let s = growing_processes_to_string(
+        &trends,
+        &ps,
+        &Passwd::default(),
+        Bytes::from_kib(1024 * 1024),
+        PROCESSES_TOP,
+    );
+    print!("{}", s);
+    assert!(s.contains("growing, 1 of 60 min window"));
+    assert_eq!(s.lines().count(), 2);
+    assert!(s.lines().nth(1).unwrap().contains("      10 "));
+    // 1 GiB at 60 MiB/h
+    assert!(s.contains("60.0"));
+}
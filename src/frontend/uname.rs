@@ -1,11 +1,76 @@
-use super::ICON_KERNEL;
-use crate::Uname;
+use super::{
+    ICON_ALPINE, ICON_ARCH, ICON_CENTOS, ICON_DEBIAN, ICON_FEDORA, ICON_GENTOO, ICON_KERNEL,
+    ICON_LINUX, ICON_MANJARO, ICON_MINT, ICON_NIXOS, ICON_OPENSUSE, ICON_REDHAT, ICON_UBUNTU,
+};
+use crate::{OsRelease, Uname};
 use std::io::Error;
 
+// get distribution icon using `ID`, `ID_LIKE` (e.g. `pop` is `ubuntu` like)
+pub fn get_distro_icon(os_release: &OsRelease) -> &'static str {
+    for id in os_release.get_ids() {
+        let icon = match id {
+            "alpine" => ICON_ALPINE,
+            "arch" | "archarm" | "endeavouros" => ICON_ARCH,
+            "centos" | "rocky" | "almalinux" => ICON_CENTOS,
+            "debian" | "raspbian" => ICON_DEBIAN,
+            "fedora" => ICON_FEDORA,
+            "gentoo" => ICON_GENTOO,
+            "linuxmint" => ICON_MINT,
+            "manjaro" => ICON_MANJARO,
+            "nixos" => ICON_NIXOS,
+            "opensuse" | "opensuse-leap" | "opensuse-tumbleweed" | "suse" | "sles" => ICON_OPENSUSE,
+            "rhel" => ICON_REDHAT,
+            "ubuntu" => ICON_UBUNTU,
+            _ => continue,
+        };
+        return icon;
+    }
+
+    ICON_LINUX
+}
+
 pub fn uname_to_string() -> Result<String, Error> {
+    let mut s = String::new();
+
     let uname = Uname::get().unwrap();
-    Ok(format!(
-        " {}  Kernel: {} {} {} Distro: {:<20} \n",
-        ICON_KERNEL, uname.sysname, uname.release, uname.machine, uname.version
-    ))
+    let build_date = uname
+        .get_build_date()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    s += &format!(
+        " {}  Kernel: {} {} {} Built: {} ({})\n",
+        ICON_KERNEL, uname.sysname, uname.release, uname.machine, build_date, uname.version
+    );
+
+    // NOTE: no os-release in minimal containers
+    let os_release = OsRelease::get().unwrap_or_default();
+    let codename = match &os_release.version_codename {
+        // already part of the name (e.g. `Debian GNU/Linux 12 (bookworm)`)
+        Some(codename) if !os_release.get_display_name().contains(codename.as_str()) => {
+            format!(" ({})", codename)
+        }
+        _ => String::new(),
+    };
+    let variant = match &os_release.variant {
+        Some(variant) if !os_release.get_display_name().contains(variant.as_str()) => {
+            format!(" {}", variant)
+        }
+        _ => String::new(),
+    };
+    s += &format!(
+        " {}  Distro: {}{}{} ID: {}\n",
+        get_distro_icon(&os_release),
+        os_release.get_display_name(),
+        codename,
+        variant,
+        os_release.get_ids().join(" ")
+    );
+
+    Ok(s)
+}
+
+#[test]
+fn uname_to_string_test() {
+    let s = uname_to_string().unwrap();
+    print!("{}", s);
 }
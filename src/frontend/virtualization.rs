@@ -0,0 +1,36 @@
+use super::{ICON_DOCKER, ICON_VIRTUALIZATION};
+use crate::Virtualization;
+use std::io::Error;
+
+// e.g. `KVM guest, Docker container`, `bare metal`
+pub fn virtualization_to_string() -> Result<String, Error> {
+    let virtualization = Virtualization::get()?;
+
+    let mut environment = Vec::new();
+    if let Some(hypervisor) = &virtualization.hypervisor {
+        environment.push(format!("{} guest", hypervisor.name()));
+    }
+    if let Some(container) = &virtualization.container {
+        environment.push(format!("{} container", container.name()));
+    }
+    if environment.is_empty() {
+        environment.push("bare metal".to_string());
+    }
+
+    let icon = match virtualization.container {
+        Some(_) => ICON_DOCKER,
+        None => ICON_VIRTUALIZATION,
+    };
+
+    Ok(format!(
+        " {}  Environment: {}\n",
+        icon,
+        environment.join(", ")
+    ))
+}
+
+#[test]
+fn virtualization_to_string_test() {
+    let s = virtualization_to_string().unwrap();
+    print!("{}", s);
+}
@@ -0,0 +1,147 @@
+use crate::{
+    get_cpuinfo, odd_even, CpuVulnerabilities, Json, KernelCmdline, Vulnerability, COLOR_CRITICAL,
+    COLOR_DEFAULT, COLOR_WARNING, ICON_SHIELD, MITIGATIONS_OPTION, MITIGATION_OPTIONS,
+    NOSMT_OPTION,
+};
+use std::io::Error;
+
+/// get status string and color (e.g. `Mitigated (TSX disabled)`)
+pub fn vulnerability_to_string(vulnerability: &Vulnerability) -> (String, &'static str) {
+    match vulnerability {
+        Vulnerability::NotAffected => ("Not affected".to_string(), COLOR_DEFAULT),
+        // NOTE: partially mitigated (e.g. `...; BHI: Vulnerable`, `..., SMT vulnerable`)
+        Vulnerability::Mitigated(how) if how.to_lowercase().contains("vulnerable") => {
+            (format!("Mitigated ({})", how), COLOR_WARNING)
+        }
+        Vulnerability::Mitigated(how) => (format!("Mitigated ({})", how), COLOR_DEFAULT),
+        Vulnerability::Vulnerable(details) if details.is_empty() => {
+            ("Vulnerable".to_string(), COLOR_CRITICAL)
+        }
+        Vulnerability::Vulnerable(details) => (format!("Vulnerable ({})", details), COLOR_CRITICAL),
+        Vulnerability::Unknown(s) => (s.to_string(), COLOR_WARNING),
+    }
+}
+
+/// relevant kernel command line options (e.g. `mitigations=auto nosmt spectre_v2=on`)
+fn get_mitigation_options(cmdline: &KernelCmdline) -> Vec<String> {
+    let mut options = vec![format!(
+        "{}={}",
+        MITIGATIONS_OPTION,
+        cmdline.get_value(MITIGATIONS_OPTION).unwrap_or("auto")
+    )];
+    if cmdline.contains(NOSMT_OPTION) {
+        options.push(NOSMT_OPTION.to_string());
+    }
+    options.extend(
+        cmdline
+            .options
+            .iter()
+            .filter(|(key, _)| MITIGATION_OPTIONS.contains(&key.as_str()))
+            .map(|(key, value)| match value {
+                Some(value) => format!("{}={}", key, value),
+                None => key.to_string(),
+            }),
+    );
+    options
+}
+
+pub fn sys_cpu_vulnerabilities_to_string() -> Result<String, Error> {
+    let mut s = String::new();
+
+    let vulnerabilities = CpuVulnerabilities::get()?;
+    let options = get_mitigation_options(&KernelCmdline::get()?);
+    let cpus = get_cpuinfo()?;
+
+    let bugs = cpus
+        .cpus
+        .first()
+        .map(|cpu| cpu.bugs.join(" "))
+        .unwrap_or_default();
+
+    s += &format!(
+        "{} {}  Kernel: {:<40} Bugs: {}\x1b[0m\n",
+        odd_even(0),
+        ICON_SHIELD,
+        options.join(" "),
+        bugs
+    );
+
+    for (i, (name, vulnerability)) in vulnerabilities.iter().enumerate() {
+        let (status, color) = vulnerability_to_string(vulnerability);
+        s += &format!(
+            "{}   {:<28} {}{}{}\x1b[0m\n",
+            odd_even(i + 1),
+            name,
+            color,
+            status,
+            COLOR_DEFAULT
+        );
+    }
+
+    Ok(s)
+}
+
+/// security report for `--json`
+/// (e.g. `{"kernel_options":[..],"bugs":[..],"vulnerabilities":{"mds":{"status":"Mitigated",..}}}`)
+pub fn sys_cpu_vulnerabilities_to_json() -> Result<Json, Error> {
+    let vulnerabilities = CpuVulnerabilities::get()?;
+    let options = get_mitigation_options(&KernelCmdline::get()?);
+    let cpus = get_cpuinfo()?;
+
+    let bugs = cpus
+        .cpus
+        .first()
+        .map(|cpu| cpu.bugs.clone())
+        .unwrap_or_default();
+
+    let vulnerabilities = vulnerabilities
+        .iter()
+        .map(|(name, vulnerability)| {
+            let (status, details) = match vulnerability {
+                Vulnerability::NotAffected => ("Not affected", String::new()),
+                Vulnerability::Mitigated(how) => ("Mitigated", how.clone()),
+                Vulnerability::Vulnerable(details) => ("Vulnerable", details.clone()),
+                Vulnerability::Unknown(s) => ("Unknown", s.clone()),
+            };
+            let value = Json::Object(vec![
+                ("status".to_string(), Json::String(status.to_string())),
+                ("details".to_string(), Json::String(details)),
+            ]);
+            (name.clone(), value)
+        })
+        .collect();
+
+    Ok(Json::Object(vec![
+        (
+            "kernel_options".to_string(),
+            Json::Array(options.into_iter().map(Json::String).collect()),
+        ),
+        (
+            "bugs".to_string(),
+            Json::Array(bugs.into_iter().map(Json::String).collect()),
+        ),
+        ("vulnerabilities".to_string(), Json::Object(vulnerabilities)),
+    ]))
+}
+
+#[test]
+fn vulnerability_to_string_test() {
+    let partial = Vulnerability::Mitigated("PTE Inversion; SMT vulnerable".to_string());
+    assert_eq!(vulnerability_to_string(&partial).1, COLOR_WARNING);
+    let full = Vulnerability::Mitigated("TSX disabled".to_string());
+    assert_eq!(vulnerability_to_string(&full).1, COLOR_DEFAULT);
+}
+
+#[test]
+fn sys_cpu_vulnerabilities_to_string_test() {
+    let s = sys_cpu_vulnerabilities_to_string().unwrap();
+    print!("{}", s);
+}
+
+#[test]
+fn sys_cpu_vulnerabilities_to_json_test() {
+    let json = sys_cpu_vulnerabilities_to_json().unwrap();
+    println!("{}", json);
+    assert!(json.get("vulnerabilities").is_some());
+    assert_eq!(json.to_string().parse::<Json>().unwrap(), json);
+}
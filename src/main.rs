@@ -19,32 +19,119 @@ const L_DATE_TIME: &str =
     "─ Date & Time ──────────────────────────────────────────────────────────────────";
 const L_SYSTEM: &str =
     "─ System ───────────────────────────────────────────────────────────────────────";
+const L_CONTAINER: &str =
+    "─ Container ────────────────────────────────────────────────────────────────────";
+const L_CONTAINERS: &str =
+    "─ Containers ───────────────────────────────────────────────────────────────────";
 const L_CPU: &str =
     "─ CPU ──────────────────────────────────────────────────────────────────────────";
 const L_MEM: &str =
     "─ Memory ───────────────────────────────────────────────────────────────────────";
+const L_PRESSURE: &str =
+    "─ Pressure ─────────────────────────────────────────────────────────────────────";
+const L_INTERRUPTS: &str =
+    "─ Interrupts ───────────────────────────────────────────────────────────────────";
+const L_PROCESSES: &str =
+    "─ Processes ────────────────────────────────────────────────────────────────────";
+const L_CGROUPS: &str =
+    "─ Cgroups ──────────────────────────────────────────────────────────────────────";
+const L_SECURITY: &str =
+    "─ Security ─────────────────────────────────────────────────────────────────────";
 const L_DISKS: &str =
     "─ Disks ────────────────────────────────────────────────────────────────────────";
 const L_NETWORK: &str =
     "─ Network ──────────────────────────────────────────────────────────────────────";
 
-//     /etc/os-release
-//     distribution_id
-//     os_version
-//     kernel_version
+// command line options (e.g. `termsysmon --sort rss --top 20 --tree --trend 120`)
+fn get_process_options() -> Result<ProcessOptions, std::io::Error> {
+    use std::io::{Error, ErrorKind};
+
+    let mut options = ProcessOptions::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        // flags without value
+        if arg == "--tree" {
+            options.tree = true;
+            continue;
+        }
+        // output format, handled in `main`
+        if arg == "--json" {
+            continue;
+        }
+
+        let value = args.next().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("missing value for `{}`", arg),
+            )
+        })?;
+        match arg.as_str() {
+            "--sort" => options.sort = value.parse()?,
+            "--top" => {
+                options.top = value
+                    .parse()
+                    .map_err(|_| Error::new(ErrorKind::InvalidInput, "invalid `--top` number"))?
+            }
+            // window in minutes
+            "--trend" => {
+                // NOTE: a zero window drops every sample, no slope at all
+                let seconds = value
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|minutes| *minutes > 0)
+                    .and_then(|minutes| minutes.checked_mul(60))
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::InvalidInput, "invalid `--trend` minutes (1 or more)")
+                    })?;
+                options.trend_window = Duration::from_secs(seconds);
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                    "unknown option `{}` (--sort cpu|rss|swap, --top N, --tree, --trend MINUTES, --json)",
+                    arg
+                ),
+                ))
+            }
+        }
+    }
+
+    Ok(options)
+}
 
 // 100ms = like conky average (0.7)
 // 500ms = like conky average (0.3)
 // 1000ms = not seen in top processes
-fn update() {
+fn update(process_options: ProcessOptions) {
     // strings produced once on start
     let mut once = String::new();
     once += &uname_to_string().unwrap();
+    once += &virtualization_to_string().unwrap();
     once += &sys_class_dmi_to_string().unwrap();
+    once += &sys_firmware_dmi_to_string().unwrap();
+
+    // security report, mitigations are set on boot
+    let security = sys_cpu_vulnerabilities_to_string().unwrap();
 
     let mut cpu_snapshot0 = CpuStats::get().unwrap();
+    let mut idle_snapshot0 = CpuIdles::get().unwrap();
+    let mut power_snapshot0 = PowercapZones::get().unwrap();
     let mut net_snapshot0 = ProcNetDevs::get().unwrap();
     let mut block_snapshot0 = SysBlockInfos::get().unwrap();
+    let mut pressure_snapshot0 = Pressures::get().unwrap();
+    let mut vmstat_snapshot0 = VmStat::get().unwrap();
+    let mut numa_snapshot0 = NumaNodes::get().unwrap();
+    let mut irq_snapshot0 = Interrupts::get().unwrap();
+    let mut softirq_snapshot0 = SoftIrqs::get().unwrap();
+    let mut ps_snapshot0 = ProcList::get().unwrap();
+    let mut memory_trends = MemoryTrends::new(process_options.trend_window);
+    let mut cgroup_snapshot0 = Cgroups::get().unwrap();
+    let mut limits_snapshot0 = CgroupLimits::get().unwrap();
+    let mut containers_snapshot0 = EngineContainers::get().unwrap();
+    // container detected once, limits are shown in containers only
+    let container = Virtualization::get().unwrap().container.is_some();
 
     // time between snapshots in s
     let dt = 5;
@@ -65,14 +152,42 @@ fn update() {
         s += &format!("{}\n", L_SYSTEM);
         s += &once;
         s += &uptime_to_string().unwrap();
+        let limits = cgroup_limits_to_string(&mut limits_snapshot0, container, dt).unwrap();
+        if !limits.is_empty() {
+            s += &format!("{}\n", L_CONTAINER);
+            s += &limits;
+        }
         s += &format!("{}\n", L_CPU);
-        s += &proc_cpuinfo_to_string(&mut cpu_snapshot0).unwrap();
+        s += &proc_cpuinfo_to_string(&mut cpu_snapshot0, &mut idle_snapshot0, dt).unwrap();
+        s += &sys_class_powercap_to_string(&mut power_snapshot0, dt).unwrap();
         s += &format!("{}\n", L_MEM);
         s += &proc_meminfo_to_string().unwrap();
+        s += &proc_swaps_to_string().unwrap();
+        s += &zram_zswap_to_string().unwrap();
+        s += &sys_devices_system_node_to_string(&mut numa_snapshot0, dt).unwrap();
+        s += &proc_vmstat_to_string(&mut vmstat_snapshot0, dt).unwrap();
+        s += &format!("{}\n", L_PRESSURE);
+        s += &proc_pressure_to_string(&mut pressure_snapshot0, dt).unwrap();
+        s += &format!("{}\n", L_INTERRUPTS);
+        s += &proc_interrupts_to_string(&mut irq_snapshot0, &mut softirq_snapshot0, dt).unwrap();
+        s += &format!("{}\n", L_PROCESSES);
+        s += &processes_to_string(&mut ps_snapshot0, &mut memory_trends, &process_options, dt)
+            .unwrap();
+        s += &format!("{}\n", L_CGROUPS);
+        s += &sys_fs_cgroup_to_string(&mut cgroup_snapshot0, dt).unwrap();
+        s += &format!("{}\n", L_SECURITY);
+        s += &security;
         s += &format!("{}\n", L_DISKS);
         s += &sys_block_to_string(&mut block_snapshot0, dt).unwrap();
         s += &format!("{}\n", L_NETWORK);
         s += &sys_class_net_to_string(&mut net_snapshot0, dt).unwrap();
+        s += &listening_sockets_to_string().unwrap();
+        // optional, container engine sockets only
+        let containers = containers_to_string(&mut containers_snapshot0, dt).unwrap();
+        if !containers.is_empty() {
+            s += &format!("{}\n", L_CONTAINERS);
+            s += &containers;
+        }
 
         print!("{}", s);
         print!("\x1b[1049l");
@@ -85,11 +200,28 @@ fn update() {
 fn update_test() {
     use crate::bench;
 
-    bench(&update, Some(100));
+    bench(&|| update(ProcessOptions::default()), Some(100));
+}
+
+// one shot report for audits (e.g. `termsysmon --json > report.json`)
+fn print_json() {
+    let json = Json::Object(vec![(
+        "security".to_string(),
+        sys_cpu_vulnerabilities_to_json().unwrap(),
+    )]);
+    println!("{}", json);
 }
 
 fn main() {
     //    calendar();
     // update();
-    update();
+    match get_process_options() {
+        Ok(_) if std::env::args().any(|arg| arg == "--json") => print_json(),
+        Ok(process_options) => update(process_options),
+        Err(e) => {
+            eprintln!("termsysmon: {}", e);
+            // usage error
+            std::process::exit(2);
+        }
+    }
 }